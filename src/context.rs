@@ -4,7 +4,124 @@
 use pyo3::prelude::*;
 use serde_json::json;
 use std::process::Command;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// Desktop session kind, used to pick the right tool family on Linux.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LinuxSession {
+    Wayland,
+    X11,
+}
+
+/// Detect the running desktop session via `XDG_SESSION_TYPE`, falling back to
+/// probing `WAYLAND_DISPLAY` vs `DISPLAY` when that variable is unset or unknown
+/// (common in nested/minimal sessions).
+fn detect_linux_session() -> LinuxSession {
+    match std::env::var("XDG_SESSION_TYPE").as_deref() {
+        Ok("wayland") => LinuxSession::Wayland,
+        Ok("x11") => LinuxSession::X11,
+        _ => {
+            if std::env::var("WAYLAND_DISPLAY").is_ok() {
+                LinuxSession::Wayland
+            } else {
+                LinuxSession::X11
+            }
+        }
+    }
+}
+
+/// Walk a `swaymsg -t get_tree` node tree and return the focused node's name
+/// and rectangle, if any. Works for any `wlroots`-based compositor that
+/// implements the sway IPC (Sway, Hyprland's sway-compat, etc.).
+fn sway_focused_node(tree: &serde_json::Value) -> Option<(String, i64, i64, i64, i64)> {
+    if tree.get("focused").and_then(|v| v.as_bool()) == Some(true) {
+        let name = tree.get("name")?.as_str()?.to_string();
+        let rect = tree.get("rect")?;
+        let x = rect.get("x")?.as_i64()?;
+        let y = rect.get("y")?.as_i64()?;
+        let width = rect.get("width")?.as_i64()?;
+        let height = rect.get("height")?.as_i64()?;
+        return Some((name, x, y, width, height));
+    }
+    for child_key in ["nodes", "floating_nodes"] {
+        if let Some(children) = tree.get(child_key).and_then(|v| v.as_array()) {
+            for child in children {
+                if let Some(found) = sway_focused_node(child) {
+                    return Some(found);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Decode a just-captured image file into raw RGBA8 bytes and base64-encode
+/// them for an in-memory result, then remove the backing file. Used when a
+/// caller asks for `return_bytes` instead of a saved path - our capture tools
+/// (grim/gnome-screenshot/screencapture/import) only know how to write to
+/// disk, so we still pass through a short-lived temp file but never hand its
+/// path back or leave it behind.
+fn frame_bytes_result(path: &str) -> Result<serde_json::Value, String> {
+    use base64::Engine;
+
+    let decoded = image::open(path);
+    let _ = std::fs::remove_file(path);
+    let img = decoded.map_err(|e| format!("Could not decode captured frame: {}", e))?;
+    let rgba = img.to_rgba8();
+    let (img_width, img_height) = rgba.dimensions();
+    let stride = img_width * 4;
+    let data_base64 = base64::engine::general_purpose::STANDARD.encode(rgba.as_raw());
+
+    Ok(json!({
+        "width": img_width,
+        "height": img_height,
+        "stride": stride,
+        "pixel_format": "rgba8",
+        "data_base64": data_base64
+    }))
+}
+
+/// True when running inside a GNOME Shell session (`XDG_CURRENT_DESKTOP` or
+/// `DESKTOP_SESSION` mentions GNOME). The Shell's own D-Bus screenshot
+/// interface works regardless of X11/Wayland and is more reliable there than
+/// shelling out to the (X11-only) `gnome-screenshot` binary.
+fn is_gnome_shell() -> bool {
+    std::env::var("XDG_CURRENT_DESKTOP").map(|d| d.to_uppercase().contains("GNOME")).unwrap_or(false)
+        || std::env::var("DESKTOP_SESSION").map(|d| d.to_uppercase().contains("GNOME")).unwrap_or(false)
+}
+
+/// Call `org.gnome.Shell.Screenshot.Screenshot` over D-Bus to take a
+/// full-screen screenshot with cursor/flash options, returning the path
+/// GNOME Shell actually wrote to.
+fn gnome_shell_screenshot(path: &str, include_cursor: bool, flash: bool) -> Result<String, String> {
+    let connection = zbus::blocking::Connection::session().map_err(|e| e.to_string())?;
+    let reply = connection
+        .call_method(
+            Some("org.gnome.Shell.Screenshot"),
+            "/org/gnome/Shell/Screenshot",
+            Some("org.gnome.Shell.Screenshot"),
+            "Screenshot",
+            &(include_cursor, flash, path),
+        )
+        .map_err(|e| e.to_string())?;
+    let (success, filename): (bool, String) = reply.body().map_err(|e| e.to_string())?;
+
+    if success {
+        Ok(filename)
+    } else {
+        Err("GNOME Shell reported screenshot failure".to_string())
+    }
+}
+
+/// Query `swaymsg -t get_tree` and return the focused window's name + geometry.
+fn sway_get_tree_focused() -> Option<(String, i64, i64, i64, i64)> {
+    let output = Command::new("swaymsg").args(["-t", "get_tree"]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let tree: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+    sway_focused_node(&tree)
+}
 
 /// Universal context getter - consolidated context information tool
 #[pyfunction]
@@ -12,6 +129,17 @@ pub fn rust_context_get(what: String) -> PyResult<String> {
     let result = match what.as_str() {
         "window" => {
             // Get active window title using system commands
+            if cfg!(target_os = "linux") && detect_linux_session() == LinuxSession::Wayland {
+                if let Some((title, ..)) = sway_get_tree_focused() {
+                    return Ok(json!({
+                        "success": true,
+                        "result": {"title": title, "method": "rust_swaymsg"},
+                        "error": null
+                    }).to_string());
+                }
+                // Unknown/unsupported compositor - fall through to the X11 path.
+            }
+
             let output = if cfg!(target_os = "linux") {
                 Command::new("xdotool")
                     .args(["getactivewindow", "getwindowname"])
@@ -91,7 +219,9 @@ pub fn rust_context_get(what: String) -> PyResult<String> {
         },
         "clipboard" => {
             // Clipboard access using system commands
-            let output = if cfg!(target_os = "linux") {
+            let output = if cfg!(target_os = "linux") && detect_linux_session() == LinuxSession::Wayland {
+                Command::new("wl-paste").output()
+            } else if cfg!(target_os = "linux") {
                 Command::new("xclip")
                     .args(["-selection", "clipboard", "-o"])
                     .output()
@@ -106,12 +236,18 @@ pub fn rust_context_get(what: String) -> PyResult<String> {
                 }).to_string());
             };
             
+            let method = if cfg!(target_os = "linux") && detect_linux_session() == LinuxSession::Wayland {
+                "rust_wlpaste"
+            } else {
+                "rust_native"
+            };
+
             match output {
                 Ok(result) if result.status.success() => {
                     let text = String::from_utf8_lossy(&result.stdout).to_string();
                     json!({
                         "success": true,
-                        "result": {"text": text, "method": "rust_native"},
+                        "result": {"text": text, "method": method},
                         "error": null
                     })
                 },
@@ -245,8 +381,32 @@ pub fn rust_context_get(what: String) -> PyResult<String> {
 }
 
 /// Universal context capture - consolidated capture tool
+///
+/// `device_index`, `width`, `height` and `pixel_format` only apply to
+/// `capture_type == "camera"`. Pass `device_index = -1` to enumerate
+/// available cameras instead of grabbing a frame.
+///
+/// When `save_path` is empty and `return_bytes` is true, `screenshot`,
+/// `window_screenshot` and `camera` return the decoded frame as base64 bytes
+/// in the JSON `result` (plus width/height/stride/pixel_format) instead of a
+/// file path, so agent pipelines can feed a frame to a vision model without
+/// a filesystem round-trip.
 #[pyfunction]
-pub fn rust_context_capture(capture_type: String, duration: i32, window_title: String, save_path: String) -> PyResult<String> {
+#[pyo3(signature = (capture_type, duration, window_title, save_path, device_index=None, width=None, height=None, pixel_format=None, return_bytes=None, include_cursor=None, flash=None))]
+pub fn rust_context_capture(
+    capture_type: String,
+    duration: i32,
+    window_title: String,
+    save_path: String,
+    device_index: Option<i32>,
+    width: Option<u32>,
+    height: Option<u32>,
+    pixel_format: Option<String>,
+    return_bytes: Option<bool>,
+    include_cursor: Option<bool>,
+    flash: Option<bool>,
+) -> PyResult<String> {
+    let want_bytes = return_bytes.unwrap_or(false) && save_path.is_empty();
     let result = match capture_type.as_str() {
         "screenshot" => {
             // Screenshot using system commands
@@ -255,13 +415,56 @@ pub fn rust_context_capture(capture_type: String, duration: i32, window_title: S
                 .unwrap()
                 .as_secs();
             
-            let final_path = if save_path.is_empty() {
+            let final_path = if want_bytes {
+                std::env::temp_dir().join(format!("que_screenshot_{}.png", timestamp)).to_string_lossy().to_string()
+            } else if save_path.is_empty() {
                 format!("screenshot_{}.png", timestamp)
             } else {
                 save_path
             };
-            
-            let output = if cfg!(target_os = "linux") {
+
+            let is_wayland = cfg!(target_os = "linux") && detect_linux_session() == LinuxSession::Wayland;
+
+            // Try the D-Bus path first on GNOME - it's the only one with cursor/flash
+            // control and the only one that works under Wayland portals - but fall
+            // through to grim/gnome-screenshot below on failure rather than giving up,
+            // since the D-Bus interface can be unavailable (e.g. sandboxed, older Shell).
+            if cfg!(target_os = "linux") && is_gnome_shell() {
+                let cursor_requested = include_cursor.unwrap_or(false);
+                match gnome_shell_screenshot(&final_path, cursor_requested, flash.unwrap_or(false)) {
+                    Ok(actual_path) => {
+                        return Ok(if want_bytes {
+                            match frame_bytes_result(&actual_path) {
+                                Ok(mut frame) => {
+                                    frame["method"] = json!("rust_gnome_shell_dbus");
+                                    frame["cursor_captured"] = json!(cursor_requested);
+                                    json!({"success": true, "result": frame, "error": null})
+                                },
+                                Err(e) => json!({"success": false, "result": null, "error": e})
+                            }
+                        } else {
+                            let file_size = std::fs::metadata(&actual_path).map(|m| m.len()).unwrap_or(0);
+                            json!({
+                                "success": true,
+                                "result": {
+                                    "path": actual_path,
+                                    "file_size_bytes": file_size,
+                                    "method": "rust_gnome_shell_dbus",
+                                    "cursor_captured": cursor_requested
+                                },
+                                "error": null
+                            })
+                        }.to_string());
+                    },
+                    Err(_) => {
+                        // Fall through to the grim/gnome-screenshot command-line paths below.
+                    }
+                }
+            }
+
+            let output = if is_wayland {
+                Command::new("grim").args([&final_path]).output()
+            } else if cfg!(target_os = "linux") {
                 Command::new("gnome-screenshot")
                     .args(["-f", &final_path])
                     .output()
@@ -276,23 +479,34 @@ pub fn rust_context_capture(capture_type: String, duration: i32, window_title: S
                     "error": "Windows screenshot not supported yet"
                 }).to_string());
             };
-            
+
+            let method = if is_wayland { "rust_grim" } else { "rust_native" };
+
             match output {
                 Ok(result) if result.status.success() => {
-                    // Get file size if file exists
-                    let file_size = std::fs::metadata(&final_path)
-                        .map(|m| m.len())
-                        .unwrap_or(0);
-                    
-                    json!({
-                        "success": true,
-                        "result": {
-                            "path": final_path,
-                            "file_size_bytes": file_size,
-                            "method": "rust_native"
-                        },
-                        "error": null
-                    })
+                    if want_bytes {
+                        match frame_bytes_result(&final_path) {
+                            Ok(mut frame) => {
+                                frame["method"] = json!(method);
+                                json!({"success": true, "result": frame, "error": null})
+                            },
+                            Err(e) => json!({"success": false, "result": null, "error": e})
+                        }
+                    } else {
+                        let file_size = std::fs::metadata(&final_path)
+                            .map(|m| m.len())
+                            .unwrap_or(0);
+
+                        json!({
+                            "success": true,
+                            "result": {
+                                "path": final_path,
+                                "file_size_bytes": file_size,
+                                "method": method
+                            },
+                            "error": null
+                        })
+                    }
                 },
                 _ => {
                     json!({
@@ -318,12 +532,73 @@ pub fn rust_context_capture(capture_type: String, duration: i32, window_title: S
                 .unwrap()
                 .as_secs();
             
-            let final_path = if save_path.is_empty() {
+            let final_path = if want_bytes {
+                std::env::temp_dir().join(format!("que_window_screenshot_{}.png", timestamp)).to_string_lossy().to_string()
+            } else if save_path.is_empty() {
                 format!("window_screenshot_{}.png", timestamp)
             } else {
                 save_path
             };
-            
+
+            if detect_linux_session() == LinuxSession::Wayland {
+                let focused = if !window_title.is_empty() {
+                    // Only the focused window is considered: it is used when its name
+                    // contains the title; other windows in the tree are not searched.
+                    sway_get_tree_focused().filter(|(name, ..)| name.contains(&window_title))
+                } else {
+                    sway_get_tree_focused()
+                };
+
+                return Ok(match focused {
+                    Some((title, x, y, width, height)) => {
+                        let geometry = format!("{},{} {}x{}", x, y, width, height);
+                        let screenshot_output = Command::new("grim")
+                            .args(["-g", &geometry, &final_path])
+                            .output();
+
+                        match screenshot_output {
+                            Ok(result) if result.status.success() => {
+                                if want_bytes {
+                                    match frame_bytes_result(&final_path) {
+                                        Ok(mut frame) => {
+                                            frame["window_title"] = json!(title);
+                                            frame["method"] = json!("rust_grim");
+                                            json!({"success": true, "result": frame, "error": null})
+                                        },
+                                        Err(e) => json!({"success": false, "result": null, "error": e})
+                                    }
+                                } else {
+                                    let file_size = std::fs::metadata(&final_path)
+                                        .map(|m| m.len())
+                                        .unwrap_or(0);
+
+                                    json!({
+                                        "success": true,
+                                        "result": {
+                                            "path": final_path,
+                                            "window_title": title,
+                                            "file_size_bytes": file_size,
+                                            "method": "rust_grim"
+                                        },
+                                        "error": null
+                                    })
+                                }
+                            },
+                            _ => json!({
+                                "success": false,
+                                "result": null,
+                                "error": "Could not capture window screenshot"
+                            })
+                        }
+                    },
+                    None => json!({
+                        "success": false,
+                        "result": null,
+                        "error": "Could not find window"
+                    })
+                }.to_string());
+            }
+
             // Get window ID first
             let window_output = if !window_title.is_empty() {
                 Command::new("xdotool")
@@ -347,20 +622,31 @@ pub fn rust_context_capture(capture_type: String, duration: i32, window_title: S
                     
                     match screenshot_output {
                         Ok(result) if result.status.success() => {
-                            let file_size = std::fs::metadata(&final_path)
-                                .map(|m| m.len())
-                                .unwrap_or(0);
-                            
-                            json!({
-                                "success": true,
-                                "result": {
-                                    "path": final_path,
-                                    "window_id": window_id,
-                                    "file_size_bytes": file_size,
-                                    "method": "rust_import"
-                                },
-                                "error": null
-                            })
+                            if want_bytes {
+                                match frame_bytes_result(&final_path) {
+                                    Ok(mut frame) => {
+                                        frame["window_id"] = json!(window_id);
+                                        frame["method"] = json!("rust_import");
+                                        json!({"success": true, "result": frame, "error": null})
+                                    },
+                                    Err(e) => json!({"success": false, "result": null, "error": e})
+                                }
+                            } else {
+                                let file_size = std::fs::metadata(&final_path)
+                                    .map(|m| m.len())
+                                    .unwrap_or(0);
+
+                                json!({
+                                    "success": true,
+                                    "result": {
+                                        "path": final_path,
+                                        "window_id": window_id,
+                                        "file_size_bytes": file_size,
+                                        "method": "rust_import"
+                                    },
+                                    "error": null
+                                })
+                            }
                         },
                         _ => {
                             json!({
@@ -381,52 +667,123 @@ pub fn rust_context_capture(capture_type: String, duration: i32, window_title: S
             }
         },
         "camera" => {
-            // Camera capture using fswebcam on Linux
-            if !cfg!(target_os = "linux") {
-                return Ok(json!({
-                    "success": false,
-                    "result": null,
-                    "error": "Camera capture only supported on Linux currently"
-                }).to_string());
+            // Camera capture via nokhwa, giving us real cross-platform device
+            // enumeration plus resolution/format negotiation instead of shelling
+            // out to fswebcam at a fixed 640x480.
+            use nokhwa::pixel_format::RgbFormat;
+            use nokhwa::utils::{
+                ApiBackend, CameraFormat, CameraIndex, FrameFormat, RequestedFormat,
+                RequestedFormatType, Resolution,
+            };
+            use nokhwa::Camera;
+
+            // device_index == -1 means "list devices" rather than capture.
+            if device_index == Some(-1) {
+                return Ok(match nokhwa::query(ApiBackend::Auto) {
+                    Ok(cameras) => {
+                        let devices: Vec<_> = cameras
+                            .iter()
+                            .map(|info| json!({
+                                "index": info.index().to_string(),
+                                "name": info.human_name(),
+                                "description": info.description()
+                            }))
+                            .collect();
+                        json!({
+                            "success": true,
+                            "result": {"devices": devices, "count": devices.len(), "method": "rust_nokhwa"},
+                            "error": null
+                        })
+                    },
+                    Err(e) => json!({
+                        "success": false,
+                        "result": null,
+                        "error": format!("Could not enumerate cameras: {}", e)
+                    })
+                }.to_string());
             }
-            
+
             let timestamp = SystemTime::now()
                 .duration_since(UNIX_EPOCH)
                 .unwrap()
                 .as_secs();
-            
+
             let final_path = if save_path.is_empty() {
                 format!("camera_{}.jpg", timestamp)
             } else {
                 save_path
             };
-            
-            let output = Command::new("fswebcam")
-                .args(["-r", "640x480", "--no-banner", &final_path])
-                .output();
-            
-            match output {
-                Ok(result) if result.status.success() => {
-                    let file_size = std::fs::metadata(&final_path)
-                        .map(|m| m.len())
-                        .unwrap_or(0);
-                    
-                    json!({
-                        "success": true,
-                        "result": {
-                            "path": final_path,
-                            "resolution": "640x480",
-                            "file_size_bytes": file_size,
-                            "method": "rust_fswebcam"
+
+            let index = CameraIndex::Index(device_index.unwrap_or(0) as u32);
+            let requested_resolution = Resolution::new(width.unwrap_or(640), height.unwrap_or(480));
+            let requested_frame_format = match pixel_format.as_deref() {
+                Some("yuyv") => FrameFormat::YUYV,
+                _ => FrameFormat::MJPEG,
+            };
+            let requested = RequestedFormat::new::<RgbFormat>(RequestedFormatType::Closest(
+                CameraFormat::new(requested_resolution, requested_frame_format, 30),
+            ));
+
+            enum CapturedFrame {
+                Saved { path: String, file_size: u64 },
+                InMemory { data_base64: String, stride: u32 },
+            }
+
+            let result = (|| -> Result<(String, u32, u32, CapturedFrame), String> {
+                let mut camera = Camera::new(index, requested).map_err(|e| e.to_string())?;
+                camera.open_stream().map_err(|e| e.to_string())?;
+                let frame = camera.frame().map_err(|e| e.to_string())?;
+                let decoded = frame.decode_image::<RgbFormat>().map_err(|e| e.to_string())?;
+                let resolution = frame.resolution();
+                let negotiated_format = format!("{:?}", camera.camera_format().format());
+
+                if want_bytes {
+                    use base64::Engine;
+                    let data_base64 = base64::engine::general_purpose::STANDARD.encode(decoded.as_raw());
+                    let captured = CapturedFrame::InMemory { data_base64, stride: resolution.width() * 3 };
+                    Ok((negotiated_format, resolution.width(), resolution.height(), captured))
+                } else {
+                    decoded.save(&final_path).map_err(|e| e.to_string())?;
+                    let file_size = std::fs::metadata(&final_path).map(|m| m.len()).unwrap_or(0);
+                    let captured = CapturedFrame::Saved { path: final_path.clone(), file_size };
+                    Ok((negotiated_format, resolution.width(), resolution.height(), captured))
+                }
+            })();
+
+            match result {
+                Ok((negotiated_format, actual_width, actual_height, captured)) => {
+                    let mut payload = json!({
+                        "device_index": device_index.unwrap_or(0),
+                        "width": actual_width,
+                        "height": actual_height,
+                        "resolution": format!("{}x{}", actual_width, actual_height),
+                        "pixel_format": negotiated_format,
+                        "method": "rust_nokhwa"
+                    });
+
+                    match captured {
+                        CapturedFrame::Saved { path, file_size } => {
+                            payload["path"] = json!(path);
+                            payload["file_size_bytes"] = json!(file_size);
                         },
-                        "error": null
-                    })
+                        CapturedFrame::InMemory { data_base64, stride } => {
+                            // `decode_image::<RgbFormat>` always hands back decoded RGB8
+                            // bytes regardless of the camera's negotiated wire format
+                            // (MJPEG/YUYV), so report the buffer's actual format here in
+                            // place of the device's negotiated wire format.
+                            payload["pixel_format"] = json!("rgb8");
+                            payload["data_base64"] = json!(data_base64);
+                            payload["stride"] = json!(stride);
+                        }
+                    }
+
+                    json!({"success": true, "result": payload, "error": null})
                 },
-                _ => {
+                Err(e) => {
                     json!({
                         "success": false,
                         "result": null,
-                        "error": "Could not capture from camera - check if fswebcam is installed and camera is available"
+                        "error": format!("Could not capture from camera: {}", e)
                     })
                 }
             }
@@ -491,6 +848,329 @@ pub fn rust_context_capture(capture_type: String, duration: i32, window_title: S
             })
         }
     };
-    
+
     Ok(result.to_string())
 }
+
+/// One display output's geometry, as reported by `xrandr`/`wlr-randr`.
+#[derive(Clone)]
+struct OutputGeometry {
+    name: String,
+    x: i64,
+    y: i64,
+    width: i64,
+    height: i64,
+}
+
+/// List connected outputs and their geometry via `xrandr --current`.
+fn list_outputs_x11() -> Vec<OutputGeometry> {
+    let output = match Command::new("xrandr").args(["--current"]).output() {
+        Ok(o) if o.status.success() => o,
+        _ => return Vec::new(),
+    };
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|line| line.contains(" connected "))
+        .filter_map(|line| {
+            let name = line.split_whitespace().next()?.to_string();
+            let geometry_part = line.split_whitespace().find(|part| part.contains('x') && part.contains('+'))?;
+            let (res, offsets) = geometry_part.split_once('+')?;
+            let (width, height) = res.split_once('x')?;
+            let mut offset_parts = offsets.split('+');
+            let x = offset_parts.next()?.parse().ok()?;
+            let y = offset_parts.next()?.parse().ok()?;
+            Some(OutputGeometry { name, x, y, width: width.parse().ok()?, height: height.parse().ok()? })
+        })
+        .collect()
+}
+
+/// List connected outputs and their geometry via `wlr-randr`.
+///
+/// `wlr-randr`'s output looks like:
+///
+/// ```text
+/// DP-1 "Some Monitor"
+///   Modes:
+///     1920x1080 px, 60.000000 Hz (preferred, current)
+///     ...
+///   Position: 0,0
+///   ...
+/// ```
+///
+/// so the resolution lives on the `Modes:` line tagged `current`, not on the
+/// `Position:` line - both have to be collected per-output before we know
+/// enough to accept it.
+fn list_outputs_wayland() -> Vec<OutputGeometry> {
+    let output = match Command::new("wlr-randr").output() {
+        Ok(o) if o.status.success() => o,
+        _ => return Vec::new(),
+    };
+
+    let text = String::from_utf8_lossy(&output.stdout);
+    let mut outputs = Vec::new();
+    let mut name: Option<String> = None;
+    let mut width: i64 = 0;
+    let mut height: i64 = 0;
+    let mut x: i64 = 0;
+    let mut y: i64 = 0;
+
+    for line in text.lines() {
+        if !line.starts_with(' ') && !line.is_empty() {
+            if let Some(finished_name) = name.take() {
+                outputs.push(OutputGeometry { name: finished_name, x, y, width, height });
+            }
+            name = line.split_whitespace().next().map(|s| s.to_string());
+            width = 0;
+            height = 0;
+            x = 0;
+            y = 0;
+        } else if line.contains("px") && line.contains("current") {
+            if let Some(mode_part) = line.trim().split_whitespace().next() {
+                if let Some((w_str, h_str)) = mode_part.split_once('x') {
+                    if let (Ok(w), Ok(h)) = (w_str.parse(), h_str.parse()) {
+                        width = w;
+                        height = h;
+                    }
+                }
+            }
+        } else if let Some(pos_start) = line.find("Position: ") {
+            let rest = &line[pos_start + "Position: ".len()..];
+            if let Some((x_str, y_str)) = rest.trim().split_once(',') {
+                if let (Ok(parsed_x), Ok(parsed_y)) = (x_str.trim().parse(), y_str.trim().parse()) {
+                    x = parsed_x;
+                    y = parsed_y;
+                }
+            }
+        }
+    }
+    if let Some(finished_name) = name.take() {
+        outputs.push(OutputGeometry { name: finished_name, x, y, width, height });
+    }
+    outputs
+}
+
+/// Find which output's bounding box contains the centre of a window rect.
+fn output_for_rect(outputs: &[OutputGeometry], x: i64, y: i64, width: i64, height: i64) -> Option<OutputGeometry> {
+    let center_x = x + width / 2;
+    let center_y = y + height / 2;
+    outputs.iter()
+        .find(|o| {
+            o.width > 0 && o.height > 0
+                && center_x >= o.x && center_x < o.x + o.width
+                && center_y >= o.y && center_y < o.y + o.height
+        })
+        .cloned()
+}
+
+/// Get the name of the currently focused workspace, sway/Wayland or X11.
+fn focused_workspace(is_wayland: bool) -> Option<String> {
+    if is_wayland {
+        let output = Command::new("swaymsg").args(["-t", "get_workspaces"]).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let workspaces: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+        workspaces.as_array()?.iter().find_map(|ws| {
+            if ws.get("focused").and_then(|v| v.as_bool()) == Some(true) {
+                ws.get("name").and_then(|v| v.as_str()).map(|s| s.to_string())
+            } else {
+                None
+            }
+        })
+    } else {
+        let output = Command::new("xdotool").args(["get_desktop"]).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+}
+
+/// Get the focused window's geometry, X11 or Wayland.
+fn focused_window_rect() -> Option<(i64, i64, i64, i64)> {
+    if detect_linux_session() == LinuxSession::Wayland {
+        return sway_get_tree_focused().map(|(_, x, y, w, h)| (x, y, w, h));
+    }
+
+    let output = Command::new("xdotool")
+        .args(["getactivewindow", "getwindowgeometry", "--shell"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let text = String::from_utf8_lossy(&output.stdout);
+    let mut x = None;
+    let mut y = None;
+    let mut width = None;
+    let mut height = None;
+    for line in text.lines() {
+        if let Some(v) = line.strip_prefix("X=") { x = v.parse().ok(); }
+        else if let Some(v) = line.strip_prefix("Y=") { y = v.parse().ok(); }
+        else if let Some(v) = line.strip_prefix("WIDTH=") { width = v.parse().ok(); }
+        else if let Some(v) = line.strip_prefix("HEIGHT=") { height = v.parse().ok(); }
+    }
+    Some((x?, y?, width?, height?))
+}
+
+/// Join per-output recording segments into `save_path`. A single segment is
+/// just renamed into place; multiple segments are almost always different
+/// resolutions (different monitors), so they're scaled/padded to a common
+/// canvas (the largest width/height seen) and stitched with ffmpeg's
+/// `concat` filter - the `-f concat` demuxer requires matching codec
+/// parameters across inputs, which monitor-switching recordings won't have.
+fn concat_segments_letterboxed(segments: &[(String, i64, i64)], save_path: &str) -> Result<(), String> {
+    match segments {
+        [] => Err("No segments were recorded".to_string()),
+        [(only_path, ..)] => {
+            std::fs::rename(only_path, save_path).map_err(|e| e.to_string())
+        },
+        _ => {
+            let canvas_w = segments.iter().map(|s| s.1).max().unwrap_or(0);
+            let canvas_h = segments.iter().map(|s| s.2).max().unwrap_or(0);
+            // libx264's default yuv420p pixel format needs even dimensions.
+            let canvas_w = canvas_w + (canvas_w % 2);
+            let canvas_h = canvas_h + (canvas_h % 2);
+
+            let mut cmd = Command::new("ffmpeg");
+            cmd.arg("-y");
+            for (path, ..) in segments {
+                cmd.args(["-i", path]);
+            }
+
+            let mut filter = String::new();
+            for i in 0..segments.len() {
+                filter.push_str(&format!(
+                    "[{i}:v]scale={w}:{h}:force_original_aspect_ratio=decrease,pad={w}:{h}:(ow-iw)/2:(oh-ih)/2,setsar=1[v{i}];",
+                    i = i, w = canvas_w, h = canvas_h
+                ));
+            }
+            for i in 0..segments.len() {
+                filter.push_str(&format!("[v{}]", i));
+            }
+            filter.push_str(&format!("concat=n={}:v=1:a=0[outv]", segments.len()));
+
+            cmd.args(["-filter_complex", &filter, "-map", "[outv]", save_path]);
+            let output = cmd.output().map_err(|e| e.to_string())?;
+            for (path, ..) in segments {
+                let _ = std::fs::remove_file(path);
+            }
+            if output.status.success() {
+                Ok(())
+            } else {
+                Err(format!("ffmpeg concat failed: {}", String::from_utf8_lossy(&output.stderr)))
+            }
+        }
+    }
+}
+
+/// Record the screen to `save_path` for `duration_secs`, following the
+/// focused window across monitors as it moves. Internally polls the focused
+/// window's geometry once a second, maps it to the containing output, and
+/// restarts the underlying recorder (`wf-recorder` on Wayland, `ffmpeg
+/// x11grab` on X11) pointed at the new output whenever it changes. Each
+/// output switch starts a fresh segment file rather than overwriting
+/// `save_path`, and all segments are stitched (with letterboxing to a
+/// common canvas) into `save_path` once recording stops. Outputs and
+/// workspaces named in the blacklists are never recorded - if the focused
+/// window ends up on one, recording pauses until it moves off.
+#[pyfunction]
+#[pyo3(signature = (duration_secs, save_path, blacklist_outputs=None, blacklist_workspaces=None))]
+pub fn rust_context_record(
+    duration_secs: u64,
+    save_path: String,
+    blacklist_outputs: Option<Vec<String>>,
+    blacklist_workspaces: Option<Vec<String>>,
+) -> PyResult<String> {
+    use std::thread::sleep;
+    use std::time::Duration;
+
+    let blacklist_outputs = blacklist_outputs.unwrap_or_default();
+    let blacklist_workspaces = blacklist_workspaces.unwrap_or_default();
+    let is_wayland = detect_linux_session() == LinuxSession::Wayland;
+    let segment_ext = std::path::Path::new(&save_path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("mp4");
+
+    let start_recorder = |output: &OutputGeometry, path: &str| -> Option<std::process::Child> {
+        if is_wayland {
+            Command::new("wf-recorder").args(["-o", &output.name, "-f", path]).spawn().ok()
+        } else {
+            let video_size = format!("{}x{}", output.width, output.height);
+            let display_offset = format!(":0.0+{},{}", output.x, output.y);
+            Command::new("ffmpeg")
+                .args(["-y", "-f", "x11grab", "-video_size", &video_size, "-i", &display_offset, path])
+                .spawn()
+                .ok()
+        }
+    };
+
+    let mut switch_events: Vec<serde_json::Value> = Vec::new();
+    let mut segments: Vec<(String, i64, i64)> = Vec::new();
+    let mut current_child: Option<std::process::Child> = None;
+    let mut current_output: Option<String> = None;
+    let start_time = Instant::now();
+
+    while start_time.elapsed().as_secs() < duration_secs {
+        if let Some((x, y, width, height)) = focused_window_rect() {
+            let outputs = if is_wayland { list_outputs_wayland() } else { list_outputs_x11() };
+            if let Some(output) = output_for_rect(&outputs, x, y, width, height) {
+                let blacklisted = blacklist_outputs.iter().any(|b| b == &output.name)
+                    || focused_workspace(is_wayland)
+                        .map(|ws| blacklist_workspaces.iter().any(|b| b == &ws))
+                        .unwrap_or(false);
+
+                if !blacklisted && current_output.as_deref() != Some(output.name.as_str()) {
+                    if let Some(mut child) = current_child.take() {
+                        let _ = child.kill();
+                        let _ = child.wait();
+                    }
+                    let segment_path = format!("{}.part{}.{}", save_path, segments.len(), segment_ext);
+                    current_child = start_recorder(&output, &segment_path);
+                    if current_child.is_some() {
+                        segments.push((segment_path, output.width, output.height));
+                    }
+                    switch_events.push(json!({
+                        "output": output.name,
+                        "elapsed_secs": start_time.elapsed().as_secs()
+                    }));
+                    current_output = Some(output.name);
+                } else if blacklisted && current_output.is_some() {
+                    if let Some(mut child) = current_child.take() {
+                        let _ = child.kill();
+                        let _ = child.wait();
+                    }
+                    current_output = None;
+                }
+            }
+        }
+        sleep(Duration::from_secs(1));
+    }
+
+    if let Some(mut child) = current_child.take() {
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+
+    let join_result = concat_segments_letterboxed(&segments, &save_path);
+    let file_size = std::fs::metadata(&save_path).map(|m| m.len()).unwrap_or(0);
+    let success = join_result.is_ok() && file_size > 0;
+
+    Ok(json!({
+        "success": success,
+        "result": {
+            "path": save_path,
+            "duration_secs": start_time.elapsed().as_secs(),
+            "output_switches": switch_events,
+            "method": if is_wayland { "rust_wf_recorder" } else { "rust_ffmpeg" }
+        },
+        "error": if success {
+            serde_json::Value::Null
+        } else {
+            json!(join_result.err().unwrap_or_else(|| "Recording produced no output - check wf-recorder/ffmpeg are installed".to_string()))
+        }
+    }).to_string())
+}
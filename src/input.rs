@@ -0,0 +1,186 @@
+//! Input tools - synthetic keyboard/mouse input
+//! Companion to the context tools: where `rust_context_get`/`rust_context_capture`
+//! observe the desktop, `rust_input_send` acts on it.
+
+use pyo3::prelude::*;
+use serde_json::json;
+use std::process::Command;
+
+/// Run a single `xdotool`/`ydotool` action and report success/error.
+fn run_x11_or_wayland_action(action: &serde_json::Value, use_ydotool: bool) -> serde_json::Value {
+    let action_type = action.get("type").and_then(|v| v.as_str()).unwrap_or("");
+
+    let output = match action_type {
+        "move" => {
+            let x = action.get("x").and_then(|v| v.as_i64()).unwrap_or(0);
+            let y = action.get("y").and_then(|v| v.as_i64()).unwrap_or(0);
+            if use_ydotool {
+                Command::new("ydotool").args(["mousemove", "-a", &x.to_string(), &y.to_string()]).output()
+            } else {
+                Command::new("xdotool").args(["mousemove", &x.to_string(), &y.to_string()]).output()
+            }
+        },
+        "click" => {
+            let button = match action.get("button").and_then(|v| v.as_str()).unwrap_or("left") {
+                "right" => "3",
+                "middle" => "2",
+                _ => "1",
+            };
+            if use_ydotool {
+                Command::new("ydotool").args(["click", if button == "1" { "0xC0" } else if button == "3" { "0xC1" } else { "0xC2" }]).output()
+            } else {
+                Command::new("xdotool").args(["click", button]).output()
+            }
+        },
+        "scroll" => {
+            let amount = action.get("amount").and_then(|v| v.as_i64()).unwrap_or(1);
+            if use_ydotool {
+                // `mousemove -w` sends a relative wheel event instead of moving the
+                // cursor; positive is scroll-up, negative is scroll-down, matching
+                // the X11 button-4/button-5 convention used below.
+                Command::new("ydotool").args(["mousemove", "-w", "--", "0", &amount.to_string()]).output()
+            } else {
+                let button = if amount < 0 { "5" } else { "4" };
+                Command::new("xdotool").args(["click", "--repeat", &amount.abs().to_string(), button]).output()
+            }
+        },
+        "key" => {
+            let key = action.get("key").and_then(|v| v.as_str()).unwrap_or("");
+            if use_ydotool {
+                Command::new("ydotool").args(["key", key]).output()
+            } else {
+                Command::new("xdotool").args(["key", key]).output()
+            }
+        },
+        "type" => {
+            let text = action.get("text").and_then(|v| v.as_str()).unwrap_or("");
+            if use_ydotool {
+                Command::new("ydotool").args(["type", text]).output()
+            } else {
+                Command::new("xdotool").args(["type", "--", text]).output()
+            }
+        },
+        _ => {
+            return json!({
+                "action": action,
+                "success": false,
+                "error": format!("Unknown action type: {}", action_type)
+            });
+        }
+    };
+
+    match output {
+        Ok(result) if result.status.success() => {
+            json!({"action": action, "success": true, "error": null})
+        },
+        Ok(result) => {
+            json!({
+                "action": action,
+                "success": false,
+                "error": String::from_utf8_lossy(&result.stderr).trim().to_string()
+            })
+        },
+        Err(e) => {
+            json!({"action": action, "success": false, "error": format!("Failed to execute input action: {}", e)})
+        }
+    }
+}
+
+/// Run a single action on macOS via `osascript` (System Events).
+fn run_macos_action(action: &serde_json::Value) -> serde_json::Value {
+    let action_type = action.get("type").and_then(|v| v.as_str()).unwrap_or("");
+
+    let script = match action_type {
+        "move" => {
+            let x = action.get("x").and_then(|v| v.as_i64()).unwrap_or(0);
+            let y = action.get("y").and_then(|v| v.as_i64()).unwrap_or(0);
+            format!("tell application \"System Events\" to set mouse location to {{{}, {}}}", x, y)
+        },
+        "click" => {
+            let click_kind = match action.get("button").and_then(|v| v.as_str()).unwrap_or("left") {
+                "right" => "right click",
+                "middle" => "middle click",
+                _ => "click",
+            };
+            format!("tell application \"System Events\" to {} at (get mouse location)", click_kind)
+        },
+        "key" => {
+            let key = action.get("key").and_then(|v| v.as_str()).unwrap_or("");
+            format!("tell application \"System Events\" to key code {}", key)
+        },
+        "type" => {
+            let text = action.get("text").and_then(|v| v.as_str()).unwrap_or("");
+            format!("tell application \"System Events\" to keystroke \"{}\"", text.replace('"', "\\\""))
+        },
+        _ => {
+            return json!({
+                "action": action,
+                "success": false,
+                "error": format!("Unknown action type: {}", action_type)
+            });
+        }
+    };
+
+    match Command::new("osascript").args(["-e", &script]).output() {
+        Ok(result) if result.status.success() => json!({"action": action, "success": true, "error": null}),
+        Ok(result) => json!({
+            "action": action,
+            "success": false,
+            "error": String::from_utf8_lossy(&result.stderr).trim().to_string()
+        }),
+        Err(e) => json!({"action": action, "success": false, "error": format!("Failed to execute input action: {}", e)})
+    }
+}
+
+/// Synthetic input - drives `xdotool`/`ydotool` on Linux (X11/Wayland) and
+/// `osascript` on macOS. Accepts a JSON array of actions, e.g.
+/// `[{"type":"move","x":100,"y":200},{"type":"click","button":"left"},{"type":"type","text":"hi"}]`,
+/// and returns per-action success/error so callers can build closed-loop
+/// observe-then-act agent workflows with `rust_context_get`.
+#[pyfunction]
+pub fn rust_input_send(actions_json: String) -> PyResult<String> {
+    let actions: Vec<serde_json::Value> = match serde_json::from_str(&actions_json) {
+        Ok(actions) => actions,
+        Err(e) => {
+            return Ok(json!({
+                "success": false,
+                "result": null,
+                "error": format!("Invalid actions JSON: {}", e)
+            }).to_string());
+        }
+    };
+
+    if actions.is_empty() {
+        return Ok(json!({
+            "success": false,
+            "result": null,
+            "error": "No actions provided"
+        }).to_string());
+    }
+
+    let use_ydotool = cfg!(target_os = "linux") && std::env::var("WAYLAND_DISPLAY").is_ok();
+
+    let results: Vec<serde_json::Value> = actions.iter()
+        .map(|action| {
+            if cfg!(target_os = "macos") {
+                run_macos_action(action)
+            } else if cfg!(target_os = "linux") {
+                run_x11_or_wayland_action(action, use_ydotool)
+            } else {
+                json!({"action": action, "success": false, "error": "Input simulation not supported on this platform"})
+            }
+        })
+        .collect();
+
+    let all_succeeded = results.iter().all(|r| r["success"].as_bool().unwrap_or(false));
+
+    Ok(json!({
+        "success": all_succeeded,
+        "result": {
+            "actions": results,
+            "total": results.len(),
+            "method": if cfg!(target_os = "macos") { "rust_osascript" } else if use_ydotool { "rust_ydotool" } else { "rust_xdotool" }
+        },
+        "error": null
+    }).to_string())
+}
@@ -9,38 +9,40 @@ mod context;
 mod utils;
 mod network;
 mod shell;
+mod input;
 
 // Re-export the functions we want to expose
 use system::{rust_system_query, rust_system_control, rust_process_manager};
-use context::{rust_context_get, rust_context_capture};
-use utils::{rust_read_file, rust_write_file, rust_list_files, rust_ping_host, rust_run_command, rust_check_internet, rust_file_manager, rust_file_search};
+use context::{rust_context_get, rust_context_capture, rust_context_record};
+use input::rust_input_send;
+use utils::{rust_read_file, rust_write_file, rust_list_files, rust_ping_host, rust_run_command, rust_check_internet, rust_file_manager, rust_file_search, rust_watch_path, rust_chunk_file};
 use network::{rust_network_tools, rust_web_browser};
 use shell::{rust_shell_execute, rust_environment_manager};
 
 // Legacy function aliases for backward compatibility
 #[pyfunction]
 fn get_system_info() -> PyResult<String> {
-    rust_system_query("overview".to_string())
+    rust_system_query("overview".to_string(), None)
 }
 
 #[pyfunction]
 fn get_battery_status() -> PyResult<String> {
-    rust_system_query("battery".to_string())
+    rust_system_query("battery".to_string(), None)
 }
 
 #[pyfunction]
 fn get_network_info() -> PyResult<String> {
-    rust_system_query("network".to_string())
+    rust_system_query("network".to_string(), None)
 }
 
 #[pyfunction]
 fn list_processes() -> PyResult<String> {
-    rust_process_manager("list".to_string(), None, None)
+    rust_process_manager("list".to_string(), None, None, None, None, None)
 }
 
 #[pyfunction]
 fn get_disk_info() -> PyResult<String> {
-    rust_system_query("disk".to_string())
+    rust_system_query("disk".to_string(), None)
 }
 
 /// Python module initialization - register all Rust functions
@@ -54,7 +56,9 @@ fn que_core_engine(m: &Bound<'_, PyModule>) -> PyResult<()> {
     // New consolidated context tools
     m.add_function(wrap_pyfunction!(rust_context_get, m)?)?;
     m.add_function(wrap_pyfunction!(rust_context_capture, m)?)?;
-    
+    m.add_function(wrap_pyfunction!(rust_context_record, m)?)?;
+    m.add_function(wrap_pyfunction!(rust_input_send, m)?)?;
+
     // New consolidated file tools
     m.add_function(wrap_pyfunction!(rust_file_manager, m)?)?;
     m.add_function(wrap_pyfunction!(rust_file_search, m)?)?;
@@ -85,6 +89,12 @@ fn que_core_engine(m: &Bound<'_, PyModule>) -> PyResult<()> {
     
     // Command execution
     m.add_function(wrap_pyfunction!(rust_run_command, m)?)?;
-    
+
+    // Filesystem watching
+    m.add_function(wrap_pyfunction!(rust_watch_path, m)?)?;
+
+    // Content-defined chunking
+    m.add_function(wrap_pyfunction!(rust_chunk_file, m)?)?;
+
     Ok(())
 }
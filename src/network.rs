@@ -6,8 +6,38 @@ use serde_json::json;
 use std::process::Command;
 
 /// Universal network tools - consolidated network operations
+///
+/// `headers` is a JSON object string (e.g. `{"Accept": "application/json"}`).
+/// `bearer_token`, `user`/`password`, and `basic_auth` (as `"user:pass"`) are
+/// mutually exclusive auth options applied to `request`/`download`, checked
+/// in that priority order. `timeout_secs` defaults to 30, `body` is sent as
+/// the raw request body for `request`, and `follow_redirects` defaults to
+/// true (set it to `false` to get the raw redirect response instead).
+/// `format` selects a specific stream (passed as `-f`) for `media_download`.
+/// `serve` roots an embedded HTTP server at `path` (background thread,
+/// tracked in a registry keyed by its bound `port` so `stop` can shut it
+/// down); `read_only` rejects any non-`GET` request against it.
 #[pyfunction]
-pub fn rust_network_tools(action: String, host: Option<String>, url: Option<String>, path: Option<String>, method: Option<String>, count: Option<i32>) -> PyResult<String> {
+#[pyo3(signature = (action, host=None, url=None, path=None, method=None, count=None, headers=None, body=None, bearer_token=None, basic_auth=None, user=None, password=None, timeout_secs=None, follow_redirects=None, port=None, format=None, read_only=None))]
+pub fn rust_network_tools(
+    action: String,
+    host: Option<String>,
+    url: Option<String>,
+    path: Option<String>,
+    method: Option<String>,
+    count: Option<i32>,
+    headers: Option<String>,
+    body: Option<String>,
+    bearer_token: Option<String>,
+    basic_auth: Option<String>,
+    user: Option<String>,
+    password: Option<String>,
+    timeout_secs: Option<u64>,
+    follow_redirects: Option<bool>,
+    port: Option<u16>,
+    format: Option<String>,
+    read_only: Option<bool>,
+) -> PyResult<String> {
     let result = match action.as_str() {
         "ping" => {
             let target_host = host.unwrap_or_default();
@@ -18,7 +48,7 @@ pub fn rust_network_tools(action: String, host: Option<String>, url: Option<Stri
                     "error": "Missing required argument: host"
                 })
             } else {
-                rust_ping_host_impl(&target_host, count.unwrap_or(4))
+                rust_ping_host_impl(&target_host, port.unwrap_or(443), count.unwrap_or(4))
             }
         },
         "download" => {
@@ -31,7 +61,17 @@ pub fn rust_network_tools(action: String, host: Option<String>, url: Option<Stri
                     "error": "Missing required arguments: url, path"
                 })
             } else {
-                rust_download_file_impl(&target_url, &target_path)
+                rust_download_file_impl(
+                    &target_url,
+                    &target_path,
+                    headers.as_deref(),
+                    bearer_token.as_deref(),
+                    basic_auth.as_deref(),
+                    user.as_deref(),
+                    password.as_deref(),
+                    timeout_secs.unwrap_or(30),
+                    follow_redirects.unwrap_or(true),
+                )
             }
         },
         "request" => {
@@ -43,7 +83,18 @@ pub fn rust_network_tools(action: String, host: Option<String>, url: Option<Stri
                     "error": "Missing required argument: url"
                 })
             } else {
-                rust_http_request_impl(&target_url, &method.unwrap_or_else(|| "GET".to_string()))
+                rust_http_request_impl(
+                    &target_url,
+                    &method.unwrap_or_else(|| "GET".to_string()),
+                    headers.as_deref(),
+                    body.as_deref(),
+                    bearer_token.as_deref(),
+                    basic_auth.as_deref(),
+                    user.as_deref(),
+                    password.as_deref(),
+                    timeout_secs.unwrap_or(30),
+                    follow_redirects.unwrap_or(true),
+                )
             }
         },
         "check_internet" => {
@@ -64,21 +115,77 @@ pub fn rust_network_tools(action: String, host: Option<String>, url: Option<Stri
                 rust_open_website_impl(&target_url)
             }
         },
+        "media_download" => {
+            let target_url = url.unwrap_or_default();
+            if target_url.is_empty() {
+                json!({
+                    "success": false,
+                    "result": serde_json::Value::Null,
+                    "error": "Missing required argument: url"
+                })
+            } else {
+                rust_media_download_impl(&target_url, path.as_deref(), format.as_deref())
+            }
+        },
+        "serve" => {
+            let serve_dir = path.unwrap_or_else(|| ".".to_string());
+            rust_serve_directory_impl(&serve_dir, host.as_deref().unwrap_or("127.0.0.1"), port.unwrap_or(0), read_only.unwrap_or(false))
+        },
+        "stop" => {
+            match port {
+                Some(p) => rust_stop_server_impl(p),
+                None => json!({
+                    "success": false,
+                    "result": serde_json::Value::Null,
+                    "error": "Missing required argument: port"
+                })
+            }
+        },
+        "parse_url" => {
+            let target_url = url.unwrap_or_default();
+            if target_url.is_empty() {
+                json!({
+                    "success": false,
+                    "result": serde_json::Value::Null,
+                    "error": "Missing required argument: url"
+                })
+            } else {
+                rust_parse_url_impl(&target_url)
+            }
+        },
         _ => {
             json!({
                 "success": false,
                 "result": serde_json::Value::Null,
-                "error": format!("Unknown action: {}. Use: ping, download, request, check_internet, public_ip, open_url", action)
+                "error": format!("Unknown action: {}. Use: ping, download, request, check_internet, public_ip, open_url, media_download, serve, stop, parse_url", action)
             })
         }
     };
-    
+
     Ok(result.to_string())
 }
 
 /// Smart web browser control - consolidated browser operations
+///
+/// `render`, `screenshot`, `get_content`, and `close` all drive a single
+/// persistent headless Chrome tab over CDP (via `headless_chrome`), reused
+/// across calls instead of launching a fresh browser process each time:
+/// `render` navigates to `url` and returns the page title and HTML content,
+/// optionally also saving a screenshot to `screenshot_path`; `screenshot`
+/// captures the current tab without navigating; `get_content` reads the
+/// current tab's title/HTML content; `close` shuts the session down. `navigate`,
+/// `search`, and `open` are unrelated to this session and just launch the
+/// OS default browser.
 #[pyfunction]
-pub fn rust_web_browser(action: String, url: Option<String>, query: Option<String>, search_engine: Option<String>, browser: Option<String>) -> PyResult<String> {
+#[pyo3(signature = (action, url=None, query=None, search_engine=None, browser=None, screenshot_path=None))]
+pub fn rust_web_browser(
+    action: String,
+    url: Option<String>,
+    query: Option<String>,
+    search_engine: Option<String>,
+    browser: Option<String>,
+    screenshot_path: Option<String>,
+) -> PyResult<String> {
     let result = match action.as_str() {
         "navigate" => {
             let target_url = url.unwrap_or_default();
@@ -92,6 +199,25 @@ pub fn rust_web_browser(action: String, url: Option<String>, query: Option<Strin
                 rust_navigate_browser_impl(&target_url, &browser.unwrap_or_else(|| "default".to_string()))
             }
         },
+        "render" => {
+            let target_url = url.unwrap_or_default();
+            if target_url.is_empty() {
+                json!({
+                    "success": false,
+                    "result": serde_json::Value::Null,
+                    "error": "Missing required argument: url"
+                })
+            } else {
+                rust_headless_render_impl(&target_url, screenshot_path.as_deref())
+            }
+        },
+        "screenshot" => {
+            let path = screenshot_path.unwrap_or_else(|| "screenshot.png".to_string());
+            rust_browser_screenshot_impl(&path)
+        },
+        "get_content" => {
+            rust_browser_get_content_impl()
+        },
         "search" => {
             let search_query = query.unwrap_or_default();
             if search_query.is_empty() {
@@ -108,221 +234,391 @@ pub fn rust_web_browser(action: String, url: Option<String>, query: Option<Strin
             rust_open_browser_impl(&url.unwrap_or_else(|| "about:blank".to_string()), &browser.unwrap_or_else(|| "default".to_string()))
         },
         "close" => {
-            json!({
-                "success": false,
-                "result": serde_json::Value::Null,
-                "error": "Browser closing not yet implemented in Rust backend"
-            })
+            rust_browser_close_impl()
         },
         _ => {
             json!({
                 "success": false,
                 "result": serde_json::Value::Null,
-                "error": format!("Unknown action: {}. Use: navigate, search, open, close", action)
+                "error": format!("Unknown action: {}. Use: navigate, render, screenshot, get_content, search, open, close", action)
             })
         }
     };
-    
+
     Ok(result.to_string())
 }
 
-// Implementation helpers
-fn rust_ping_host_impl(host: &str, count: i32) -> serde_json::Value {
-    use std::process::Command;
-    
-    // Determine ping command based on OS
-    let mut cmd = Command::new("ping");
-    
-    #[cfg(target_os = "windows")]
-    {
-        cmd.args(["-n", &count.to_string(), host]);
+/// A headless Chrome instance and its single reused tab. Kept alive in
+/// `browser_session()` across calls so `render`/`screenshot`/`get_content`
+/// act on the same page instead of paying Chrome's launch cost every time.
+/// Dropping the `Browser` (on `close`) kills the process.
+struct BrowserSession {
+    #[allow(dead_code)]
+    browser: headless_chrome::Browser,
+    tab: std::sync::Arc<headless_chrome::Tab>,
+}
+
+static BROWSER_SESSION: std::sync::OnceLock<std::sync::Mutex<Option<BrowserSession>>> = std::sync::OnceLock::new();
+
+fn browser_session() -> &'static std::sync::Mutex<Option<BrowserSession>> {
+    BROWSER_SESSION.get_or_init(|| std::sync::Mutex::new(None))
+}
+
+/// Return the shared tab, launching a headless browser for it on first use.
+fn ensure_tab() -> Result<std::sync::Arc<headless_chrome::Tab>, String> {
+    let mut guard = browser_session().lock().unwrap();
+    if guard.is_none() {
+        let browser = headless_chrome::Browser::default().map_err(|e| format!("Failed to launch headless browser: {}", e))?;
+        let tab = browser.new_tab().map_err(|e| format!("Failed to open tab: {}", e))?;
+        *guard = Some(BrowserSession { browser, tab });
     }
-    
-    #[cfg(not(target_os = "windows"))]
-    {
-        cmd.args(["-c", &count.to_string(), host]);
+    Ok(guard.as_ref().unwrap().tab.clone())
+}
+
+/// Navigate the shared headless Chrome tab to `url` via the Chrome DevTools
+/// Protocol, returning its title and rendered HTML content, and optionally
+/// saving a screenshot of the visible viewport to `screenshot_path`.
+fn rust_headless_render_impl(url: &str, screenshot_path: Option<&str>) -> serde_json::Value {
+    use headless_chrome::protocol::cdp::Page::CaptureScreenshotFormatOption;
+
+    let tab = match ensure_tab() {
+        Ok(t) => t,
+        Err(e) => return json!({"success": false, "result": serde_json::Value::Null, "error": e}),
+    };
+
+    if let Err(e) = tab.navigate_to(url).and_then(|t| t.wait_until_navigated()) {
+        return json!({"success": false, "result": serde_json::Value::Null, "error": format!("Navigation failed: {}", e)});
     }
-    
-    match cmd.output() {
-        Ok(output) => {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            let success = output.status.success();
-            
-            // Parse ping statistics
-            let mut stats = json!({
-                "packets_sent": count,
-                "packets_received": 0,
-                "packet_loss": 100.0,
-                "min_time": null,
-                "max_time": null,
-                "avg_time": null
-            });
-            
-            // Simple parsing for packet statistics
-            let output_str = stdout.to_lowercase();
-            if output_str.contains("packets transmitted") || output_str.contains("packets: sent") {
-                // Extract received packets count
-                for line in stdout.lines() {
-                    if line.contains("received") && (line.contains("transmitted") || line.contains("Sent")) {
-                        let parts: Vec<&str> = line.split_whitespace().collect();
-                        for (i, part) in parts.iter().enumerate() {
-                            if part.contains("received") && i > 0 {
-                                if let Ok(received) = parts[i-1].parse::<i32>() {
-                                    stats["packets_received"] = json!(received);
-                                    let loss = ((count - received) as f64 / count as f64) * 100.0;
-                                    stats["packet_loss"] = json!(loss);
-                                    break;
-                                }
-                            }
-                        }
-                    }
+
+    let title = tab.get_title().unwrap_or_default();
+    let content = tab.get_content().unwrap_or_default();
+
+    let mut result = json!({
+        "url": url,
+        "title": title,
+        "content": content,
+        "content_length": content.len(),
+        "method": "rust_headless_chrome"
+    });
+
+    if let Some(path) = screenshot_path {
+        match tab.capture_screenshot(CaptureScreenshotFormatOption::Png, None, None, true) {
+            Ok(data) => {
+                if std::fs::write(path, &data).is_ok() {
+                    result["screenshot_path"] = json!(path);
                 }
-            }
-            
-            json!({
-                "success": success,
+            },
+            Err(_) => {}
+        }
+    }
+
+    json!({"success": true, "result": result, "error": null})
+}
+
+/// Capture a screenshot of the shared tab's visible viewport, without
+/// navigating it first. Requires the session to already have a page loaded
+/// (via a prior `render`).
+fn rust_browser_screenshot_impl(path: &str) -> serde_json::Value {
+    use headless_chrome::protocol::cdp::Page::CaptureScreenshotFormatOption;
+
+    let tab = match ensure_tab() {
+        Ok(t) => t,
+        Err(e) => return json!({"success": false, "result": serde_json::Value::Null, "error": e}),
+    };
+
+    match tab.capture_screenshot(CaptureScreenshotFormatOption::Png, None, None, true) {
+        Ok(data) => match std::fs::write(path, &data) {
+            Ok(_) => json!({
+                "success": true,
                 "result": {
-                    "host": host,
-                    "count": count,
-                    "statistics": stats,
-                    "reachable": success,
-                    "output": stdout,
-                    "method": "rust_ping"
+                    "screenshot_path": path,
+                    "url": tab.get_url(),
+                    "method": "rust_headless_chrome"
                 },
-                "error": if success { serde_json::Value::Null } else { json!(stderr) }
-            })
+                "error": null
+            }),
+            Err(e) => json!({"success": false, "result": serde_json::Value::Null, "error": format!("Failed to write screenshot: {}", e)}),
         },
-        Err(e) => {
-            json!({
-                "success": false,
-                "result": serde_json::Value::Null,
-                "error": format!("Failed to execute ping: {}", e)
-            })
+        Err(e) => json!({"success": false, "result": serde_json::Value::Null, "error": format!("Failed to capture screenshot: {}", e)}),
+    }
+}
+
+/// Read the shared tab's current title and HTML content without navigating.
+fn rust_browser_get_content_impl() -> serde_json::Value {
+    let tab = match ensure_tab() {
+        Ok(t) => t,
+        Err(e) => return json!({"success": false, "result": serde_json::Value::Null, "error": e}),
+    };
+
+    let title = tab.get_title().unwrap_or_default();
+    let content = tab.get_content().unwrap_or_default();
+
+    json!({
+        "success": true,
+        "result": {
+            "url": tab.get_url(),
+            "title": title,
+            "content": content,
+            "content_length": content.len(),
+            "method": "rust_headless_chrome"
+        },
+        "error": null
+    })
+}
+
+/// Shut down the shared headless browser session, if one is running.
+fn rust_browser_close_impl() -> serde_json::Value {
+    let mut guard = browser_session().lock().unwrap();
+    let was_open = guard.take().is_some();
+
+    json!({
+        "success": true,
+        "result": {
+            "action": if was_open { "closed" } else { "already_closed" }
+        },
+        "error": null
+    })
+}
+
+// Implementation helpers
+
+/// Attempt a single TCP connection to `host:port`, returning the round-trip
+/// time on success. Used as a pure-Rust, no-shell-out stand-in for ICMP
+/// ping/reachability checks (raw ICMP sockets require elevated privileges
+/// we can't assume here; a TCP connect is a reasonable proxy, and 443 is a
+/// reasonable default port since it's open on essentially every reachable
+/// host).
+fn tcp_probe(host: &str, port: u16, timeout: std::time::Duration) -> Result<std::time::Duration, String> {
+    use std::net::{TcpStream, ToSocketAddrs};
+
+    let addr = (host, port)
+        .to_socket_addrs()
+        .map_err(|e| format!("Could not resolve host: {}", e))?
+        .next()
+        .ok_or_else(|| format!("No addresses found for host: {}", host))?;
+
+    let start = std::time::Instant::now();
+    TcpStream::connect_timeout(&addr, timeout).map_err(|e| e.to_string())?;
+    Ok(start.elapsed())
+}
+
+fn rust_ping_host_impl(host: &str, port: u16, count: i32) -> serde_json::Value {
+    use std::time::Duration;
+
+    let count = count.max(1);
+    let mut received = 0i32;
+    let mut latencies_ms: Vec<f64> = Vec::new();
+    let mut lines: Vec<String> = Vec::new();
+
+    for seq in 1..=count {
+        match tcp_probe(host, port, Duration::from_secs(2)) {
+            Ok(rtt) => {
+                let ms = rtt.as_secs_f64() * 1000.0;
+                latencies_ms.push(ms);
+                received += 1;
+                lines.push(format!("Reply from {} (seq={}): time={:.2}ms", host, seq, ms));
+            }
+            Err(e) => {
+                lines.push(format!("seq={} failed: {}", seq, e));
+            }
         }
     }
+
+    let success = received > 0;
+    let packet_loss = ((count - received) as f64 / count as f64) * 100.0;
+    let stats = json!({
+        "packets_sent": count,
+        "packets_received": received,
+        "packet_loss": packet_loss,
+        "min_time": latencies_ms.iter().cloned().fold(f64::INFINITY, f64::min).is_finite()
+            .then(|| latencies_ms.iter().cloned().fold(f64::INFINITY, f64::min)),
+        "max_time": latencies_ms.iter().cloned().fold(f64::NEG_INFINITY, f64::max).is_finite()
+            .then(|| latencies_ms.iter().cloned().fold(f64::NEG_INFINITY, f64::max)),
+        "avg_time": if latencies_ms.is_empty() { None } else { Some(latencies_ms.iter().sum::<f64>() / latencies_ms.len() as f64) }
+    });
+
+    json!({
+        "success": success,
+        "result": {
+            "host": host,
+            "port": port,
+            "count": count,
+            "statistics": stats,
+            "reachable": success,
+            "output": lines.join("\n"),
+            "method": "rust_tcp_probe"
+        },
+        "error": if success { serde_json::Value::Null } else { json!(format!("Host {} unreachable", host)) }
+    })
+}
+
+/// Build a reqwest blocking client and apply the shared header/auth/timeout
+/// options used by both `request` and `download`. Auth options are applied
+/// in priority order: `bearer_token`, then `user`/`password`, then the
+/// combined `basic_auth` ("user:pass") string.
+fn build_request(
+    client: &reqwest::blocking::Client,
+    method: &str,
+    url: &str,
+    headers: Option<&str>,
+    bearer_token: Option<&str>,
+    basic_auth: Option<&str>,
+    user: Option<&str>,
+    password: Option<&str>,
+) -> Result<reqwest::blocking::RequestBuilder, String> {
+    let method = method.parse::<reqwest::Method>().map_err(|e| format!("Invalid HTTP method: {}", e))?;
+    let mut builder = client.request(method, url);
+
+    if let Some(headers_json) = headers {
+        let parsed: std::collections::HashMap<String, String> =
+            serde_json::from_str(headers_json).map_err(|e| format!("Invalid headers JSON: {}", e))?;
+        for (key, value) in parsed {
+            builder = builder.header(key, value);
+        }
+    }
+
+    if let Some(token) = bearer_token {
+        builder = builder.bearer_auth(token);
+    } else if let Some(user) = user {
+        builder = builder.basic_auth(user, password);
+    } else if let Some(creds) = basic_auth {
+        let (user, pass) = creds.split_once(':')
+            .ok_or_else(|| "Invalid basic_auth: expected \"user:pass\"".to_string())?;
+        builder = builder.basic_auth(user, Some(pass));
+    }
+
+    Ok(builder)
 }
 
-fn rust_download_file_impl(url: &str, path: &str) -> serde_json::Value {
-    // For now, use curl command as Rust HTTP client would require additional dependencies
+fn rust_download_file_impl(
+    url: &str,
+    path: &str,
+    headers: Option<&str>,
+    bearer_token: Option<&str>,
+    basic_auth: Option<&str>,
+    user: Option<&str>,
+    password: Option<&str>,
+    timeout_secs: u64,
+    follow_redirects: bool,
+) -> serde_json::Value {
     use std::fs;
+    use std::io::Write;
     use std::path::Path;
-    
-    // Create parent directories if needed
+
     if let Some(parent) = Path::new(path).parent() {
         let _ = fs::create_dir_all(parent);
     }
-    
-    // Use curl for downloading
-    let output = Command::new("curl")
-        .args(["-L", "-o", path, url])
-        .output();
-    
-    match output {
-        Ok(result) => {
-            if result.status.success() {
-                // Get file size
-                match fs::metadata(path) {
-                    Ok(metadata) => {
-                        let file_size = metadata.len();
-                        json!({
-                            "success": true,
-                            "result": {
-                                "url": url,
-                                "path": path,
-                                "size": file_size,
-                                "size_mb": (file_size as f64 / (1024.0 * 1024.0) * 100.0).round() / 100.0,
-                                "action": "downloaded",
-                                "method": "rust_curl"
-                            },
-                            "error": serde_json::Value::Null
-                        })
-                    },
-                    Err(e) => {
-                        json!({
-                            "success": false,
-                            "result": serde_json::Value::Null,
-                            "error": format!("File downloaded but could not get size: {}", e)
-                        })
+
+    let redirect_policy = if follow_redirects { reqwest::redirect::Policy::default() } else { reqwest::redirect::Policy::none() };
+    let client = match reqwest::blocking::Client::builder()
+        .timeout(std::time::Duration::from_secs(timeout_secs))
+        .redirect(redirect_policy)
+        .build()
+    {
+        Ok(c) => c,
+        Err(e) => return json!({"success": false, "result": null, "error": format!("Failed to build HTTP client: {}", e)}),
+    };
+
+    let request = match build_request(&client, "GET", url, headers, bearer_token, basic_auth, user, password) {
+        Ok(r) => r,
+        Err(e) => return json!({"success": false, "result": null, "error": e}),
+    };
+
+    match request.send().and_then(|r| r.error_for_status()) {
+        Ok(response) => {
+            match response.bytes() {
+                Ok(bytes) => {
+                    match fs::File::create(path).and_then(|mut f| f.write_all(&bytes)) {
+                        Ok(_) => {
+                            let file_size = bytes.len() as u64;
+                            json!({
+                                "success": true,
+                                "result": {
+                                    "url": url,
+                                    "path": path,
+                                    "size": file_size,
+                                    "size_mb": (file_size as f64 / (1024.0 * 1024.0) * 100.0).round() / 100.0,
+                                    "action": "downloaded",
+                                    "method": "rust_reqwest"
+                                },
+                                "error": serde_json::Value::Null
+                            })
+                        },
+                        Err(e) => json!({"success": false, "result": null, "error": format!("Failed to write downloaded file: {}", e)})
                     }
-                }
-            } else {
-                let stderr = String::from_utf8_lossy(&result.stderr);
-                json!({
-                    "success": false,
-                    "result": serde_json::Value::Null,
-                    "error": format!("Download failed: {}", stderr)
-                })
+                },
+                Err(e) => json!({"success": false, "result": null, "error": format!("Failed to read response body: {}", e)})
             }
         },
-        Err(e) => {
-            json!({
-                "success": false,
-                "result": serde_json::Value::Null,
-                "error": format!("Failed to execute curl: {}", e)
-            })
-        }
+        Err(e) => json!({"success": false, "result": null, "error": format!("Download failed: {}", e)})
     }
 }
 
-fn rust_http_request_impl(url: &str, method: &str) -> serde_json::Value {
-    // Use curl for HTTP requests
-    let mut cmd = Command::new("curl");
-    cmd.args(["-s", "-i", "-X", method, url]);
-    
-    match cmd.output() {
-        Ok(output) => {
-            let response = String::from_utf8_lossy(&output.stdout);
-            let success = output.status.success();
-            
-            if success {
-                // Parse response (simple parsing)
-                let mut headers = std::collections::HashMap::new();
-                let mut content = String::new();
-                let mut status_code = 200;
-                let mut in_headers = true;
-                
-                for line in response.lines() {
-                    if in_headers {
-                        if line.is_empty() {
-                            in_headers = false;
-                        } else if line.starts_with("HTTP/") {
-                            // Extract status code
-                            let parts: Vec<&str> = line.split_whitespace().collect();
-                            if parts.len() >= 2 {
-                                status_code = parts[1].parse().unwrap_or(200);
-                            }
-                        } else if line.contains(':') {
-                            let parts: Vec<&str> = line.splitn(2, ':').collect();
-                            if parts.len() == 2 {
-                                headers.insert(parts[0].trim().to_string(), parts[1].trim().to_string());
-                            }
-                        }
-                    } else {
-                        content.push_str(line);
-                        content.push('\n');
-                    }
-                }
-                
-                json!({
-                    "success": true,
-                    "result": {
-                        "url": url,
-                        "method": method,
-                        "status_code": status_code,
-                        "headers": headers,
-                        "content": content.trim(),
-                        "content_length": content.len(),
-                        "method": "rust_curl"
-                    },
-                    "error": null
-                })
-            } else {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                json!({
+fn rust_http_request_impl(
+    url: &str,
+    method: &str,
+    headers: Option<&str>,
+    body: Option<&str>,
+    bearer_token: Option<&str>,
+    basic_auth: Option<&str>,
+    user: Option<&str>,
+    password: Option<&str>,
+    timeout_secs: u64,
+    follow_redirects: bool,
+) -> serde_json::Value {
+    let redirect_policy = if follow_redirects { reqwest::redirect::Policy::default() } else { reqwest::redirect::Policy::none() };
+    let client = match reqwest::blocking::Client::builder()
+        .timeout(std::time::Duration::from_secs(timeout_secs))
+        .redirect(redirect_policy)
+        .build()
+    {
+        Ok(c) => c,
+        Err(e) => return json!({"success": false, "result": serde_json::Value::Null, "error": format!("Failed to build HTTP client: {}", e)}),
+    };
+
+    let mut request = match build_request(&client, method, url, headers, bearer_token, basic_auth, user, password) {
+        Ok(r) => r,
+        Err(e) => return json!({"success": false, "result": serde_json::Value::Null, "error": e}),
+    };
+
+    if let Some(body) = body {
+        request = request.body(body.to_string());
+    }
+
+    match request.send() {
+        Ok(response) => {
+            let status_code = response.status().as_u16();
+            let response_headers: std::collections::HashMap<String, String> = response
+                .headers()
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or_default().to_string()))
+                .collect();
+            let content_length = response
+                .headers()
+                .get(reqwest::header::CONTENT_LENGTH)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse::<u64>().ok());
+
+            match response.text() {
+                Ok(content) => {
+                    json!({
+                        "success": status_code < 400,
+                        "result": {
+                            "url": url,
+                            "method": method,
+                            "status_code": status_code,
+                            "headers": response_headers,
+                            "content": content,
+                            "content_length": content_length.unwrap_or(content.len() as u64),
+                            "method": "rust_reqwest"
+                        },
+                        "error": null
+                    })
+                },
+                Err(e) => json!({
                     "success": false,
                     "result": serde_json::Value::Null,
-                    "error": format!("HTTP request failed: {}", stderr)
+                    "error": format!("Failed to read response body: {}", e)
                 })
             }
         },
@@ -330,55 +626,387 @@ fn rust_http_request_impl(url: &str, method: &str) -> serde_json::Value {
             json!({
                 "success": false,
                 "result": serde_json::Value::Null,
-                "error": format!("Failed to execute curl: {}", e)
+                "error": format!("HTTP request failed: {}", e)
             })
         }
     }
 }
 
 fn rust_check_internet_impl() -> serde_json::Value {
-    let test_hosts = vec!["8.8.8.8", "1.1.1.1", "google.com"];
+    // DNS resolvers are probed on :53 (their actual service port); the HTTP
+    // fallback host is probed on :443.
+    let test_hosts = vec![("8.8.8.8", 53u16), ("1.1.1.1", 53u16), ("google.com", 443u16)];
     let mut connected = false;
     let mut results = Vec::new();
-    
-    for host in test_hosts {
-        let mut cmd = Command::new("ping");
-        
-        #[cfg(target_os = "windows")]
-        {
-            cmd.args(["-n", "1", "-w", "3000", host]);
-        }
-        
-        #[cfg(not(target_os = "windows"))]
-        {
-            cmd.args(["-c", "1", "-W", "3", host]);
-        }
-        
-        let success = cmd.output()
-            .map(|output| output.status.success())
-            .unwrap_or(false);
-        
+
+    for (host, port) in test_hosts {
+        let success = tcp_probe(host, port, std::time::Duration::from_secs(3)).is_ok();
+
         results.push(json!({
             "host": host,
+            "port": port,
             "reachable": success
         }));
-        
+
         if success {
             connected = true;
         }
     }
-    
+
     json!({
         "success": true,
         "result": {
             "connected": connected,
             "tests": results,
-            "method": "rust_ping_test"
+            "method": "rust_tcp_probe"
         },
         "error": null
     })
 }
 
+/// Build a `Command` for an external media tool. On Windows this sets the
+/// `CREATE_NO_WINDOW` creation flag so a console window doesn't flash up
+/// behind the caller.
+fn media_command(program: &str) -> Command {
+    let mut cmd = Command::new(program);
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+    cmd
+}
+
+/// Probe a media URL via `yt-dlp --dump-single-json`, returning structured
+/// metadata (title, extension, duration, uploader, and the list of
+/// available `formats`) before ever downloading anything. A URL whose
+/// metadata contains an `entries` array is a playlist: one summary entry is
+/// returned per item instead of attempting a download. For a single item,
+/// passing `output_path` performs the actual download, with `format` (if
+/// given) passed through as `-f` to pick a specific stream.
+fn rust_media_download_impl(url: &str, output_path: Option<&str>, format: Option<&str>) -> serde_json::Value {
+    let metadata_output = media_command("yt-dlp")
+        .args(["--dump-single-json", "--no-playlist", url])
+        .output();
+
+    let metadata_output = match metadata_output {
+        Ok(o) if o.status.success() => o,
+        Ok(o) => {
+            let stderr = String::from_utf8_lossy(&o.stderr);
+            return json!({"success": false, "result": serde_json::Value::Null, "error": format!("yt-dlp metadata lookup failed: {}", stderr.trim())});
+        },
+        Err(e) => return json!({"success": false, "result": serde_json::Value::Null, "error": format!("Failed to execute yt-dlp: {}", e)}),
+    };
+
+    let metadata: serde_json::Value = match serde_json::from_slice(&metadata_output.stdout) {
+        Ok(v) => v,
+        Err(e) => return json!({"success": false, "result": serde_json::Value::Null, "error": format!("Could not parse yt-dlp metadata: {}", e)}),
+    };
+
+    if let Some(entries) = metadata.get("entries").and_then(|e| e.as_array()) {
+        let items: Vec<serde_json::Value> = entries.iter().map(|entry| json!({
+            "title": entry.get("title"),
+            "url": entry.get("webpage_url").or_else(|| entry.get("url")),
+            "duration_seconds": entry.get("duration"),
+            "uploader": entry.get("uploader")
+        })).collect();
+
+        return json!({
+            "success": true,
+            "result": {
+                "url": url,
+                "is_playlist": true,
+                "entry_count": items.len(),
+                "entries": items,
+                "method": "rust_ytdlp"
+            },
+            "error": null
+        });
+    }
+
+    let formats: Vec<serde_json::Value> = metadata.get("formats")
+        .and_then(|f| f.as_array())
+        .map(|list| list.iter().map(|f| json!({
+            "format_id": f.get("format_id"),
+            "resolution": f.get("resolution"),
+            "filesize": f.get("filesize").or_else(|| f.get("filesize_approx")),
+            "vcodec": f.get("vcodec"),
+            "acodec": f.get("acodec")
+        })).collect())
+        .unwrap_or_default();
+
+    let mut result = json!({
+        "url": url,
+        "is_playlist": false,
+        "title": metadata.get("title"),
+        "extension": metadata.get("ext"),
+        "duration_seconds": metadata.get("duration"),
+        "uploader": metadata.get("uploader"),
+        "formats": formats,
+        "downloaded": false,
+        "method": "rust_ytdlp"
+    });
+
+    let Some(output_path) = output_path else {
+        return json!({"success": true, "result": result, "error": null});
+    };
+
+    let mut download = media_command("yt-dlp");
+    download.args(["-o", output_path, "--print-json", "--no-simulate", "--no-playlist"]);
+    if let Some(format) = format {
+        download.args(["-f", format]);
+    }
+    download.arg(url);
+
+    match download.output() {
+        Ok(dl) if dl.status.success() => {
+            let stdout = String::from_utf8_lossy(&dl.stdout);
+            let metadata_line = stdout.lines().last().unwrap_or("");
+            if let Ok(dl_metadata) = serde_json::from_str::<serde_json::Value>(metadata_line) {
+                result["filepath"] = dl_metadata.get("_filename").or_else(|| dl_metadata.get("filepath")).cloned().unwrap_or(serde_json::Value::Null);
+            }
+            result["downloaded"] = json!(true);
+            json!({"success": true, "result": result, "error": null})
+        },
+        Ok(dl) => {
+            let stderr = String::from_utf8_lossy(&dl.stderr);
+            json!({"success": false, "result": serde_json::Value::Null, "error": format!("yt-dlp download failed: {}", stderr.trim())})
+        },
+        Err(e) => json!({"success": false, "result": serde_json::Value::Null, "error": format!("Failed to execute yt-dlp: {}", e)})
+    }
+}
+
+/// Running `serve` instances, keyed by the port they're bound to, so a
+/// later `stop` action can find and shut down the right background thread.
+static SERVERS: std::sync::OnceLock<std::sync::Mutex<std::collections::HashMap<u16, std::sync::Arc<std::sync::atomic::AtomicBool>>>> = std::sync::OnceLock::new();
+
+fn servers() -> &'static std::sync::Mutex<std::collections::HashMap<u16, std::sync::Arc<std::sync::atomic::AtomicBool>>> {
+    SERVERS.get_or_init(|| std::sync::Mutex::new(std::collections::HashMap::new()))
+}
+
+/// Coarse extension -> category mapping used for the directory index page.
+fn category_for_extension(ext: &str) -> &'static str {
+    match ext.to_lowercase().as_str() {
+        "png" | "jpg" | "jpeg" | "gif" | "svg" | "webp" | "bmp" | "ico" => "image",
+        "mp4" | "mov" | "avi" | "mkv" | "webm" => "video",
+        "mp3" | "wav" | "flac" | "ogg" | "m4a" => "audio",
+        "pdf" | "doc" | "docx" | "txt" | "md" | "rtf" => "document",
+        "zip" | "tar" | "gz" | "bz2" | "7z" | "rar" => "archive",
+        "rs" | "py" | "js" | "ts" | "go" | "java" | "c" | "cpp" | "h" | "rb" | "sh" | "json" | "toml" | "yaml" | "yml" | "html" | "css" => "code",
+        "" => "unknown",
+        _ => "other",
+    }
+}
+
+/// `Content-Type` by extension for the file types this server is likely to
+/// be asked for; anything unrecognized falls back to a generic binary type.
+fn content_type_for(path: &std::path::Path) -> &'static str {
+    match path.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase().as_str() {
+        "html" | "htm" => "text/html; charset=utf-8",
+        "css" => "text/css",
+        "js" => "application/javascript",
+        "json" => "application/json",
+        "txt" | "md" => "text/plain; charset=utf-8",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "svg" => "image/svg+xml",
+        "webp" => "image/webp",
+        "pdf" => "application/pdf",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "zip" => "application/zip",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Render a categorized directory index for `dir`, grouping entries by
+/// `category_for_extension` instead of a bare flat file list.
+fn render_directory_listing(dir: &std::path::Path, request_path: &str) -> String {
+    let mut categorized: std::collections::BTreeMap<&'static str, Vec<String>> = std::collections::BTreeMap::new();
+
+    if let Ok(entries) = std::fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+            let category = if is_dir {
+                "directory"
+            } else {
+                let ext = std::path::Path::new(&name).extension().and_then(|e| e.to_str()).unwrap_or("");
+                category_for_extension(ext)
+            };
+            categorized.entry(category).or_default().push(name);
+        }
+    }
+
+    let mut html = format!("<html><head><title>Index of {0}</title></head><body><h1>Index of {0}</h1>", request_path);
+    for (category, mut names) in categorized {
+        names.sort();
+        html.push_str(&format!("<h2>{}</h2><ul>", category));
+        for name in names {
+            html.push_str(&format!("<li><a href=\"{}\">{}</a></li>", name, name));
+        }
+        html.push_str("</ul>");
+    }
+    html.push_str("</body></html>");
+    html
+}
+
+/// Parse a `Range: bytes=start-end` header value against `file_size`,
+/// returning an inclusive `(start, end)` byte range, or `None` if the header
+/// is absent, malformed, or out of bounds (in which case the caller should
+/// fall back to serving the whole file).
+fn parse_range(value: &str, file_size: u64) -> Option<(u64, u64)> {
+    if file_size == 0 {
+        return None;
+    }
+    let spec = value.strip_prefix("bytes=")?;
+    let (start_str, end_str) = spec.split_once('-')?;
+    let start: u64 = if start_str.is_empty() { 0 } else { start_str.parse().ok()? };
+    let end: u64 = if end_str.is_empty() { file_size - 1 } else { end_str.parse().ok()? };
+    if start > end || end >= file_size {
+        return None;
+    }
+    Some((start, end))
+}
+
+/// Handle one request against `serve_root`: directory listing, range
+/// requests, and plain whole-file responses, all confined to `serve_root`
+/// (anything resolving outside it, e.g. via `..`, is rejected as 404).
+fn handle_serve_request(request: tiny_http::Request, serve_root: &std::path::Path, read_only: bool) {
+    use std::io::{Read, Seek, SeekFrom};
+
+    if read_only && request.method() != &tiny_http::Method::Get {
+        let _ = request.respond(tiny_http::Response::from_string("Method Not Allowed").with_status_code(405));
+        return;
+    }
+
+    let request_path = request.url().to_string();
+    let requested = request_path.trim_start_matches('/');
+    let candidate = if requested.is_empty() { serve_root.to_path_buf() } else { serve_root.join(requested) };
+
+    let resolved = match std::fs::canonicalize(&candidate) {
+        Ok(p) if p.starts_with(serve_root) => p,
+        _ => {
+            let _ = request.respond(tiny_http::Response::from_string("Not Found").with_status_code(404));
+            return;
+        }
+    };
+
+    if resolved.is_dir() {
+        let listing = render_directory_listing(&resolved, &request_path);
+        let header = tiny_http::Header::from_bytes(&b"Content-Type"[..], &b"text/html; charset=utf-8"[..]).unwrap();
+        let _ = request.respond(tiny_http::Response::from_string(listing).with_header(header));
+        return;
+    }
+
+    if !resolved.is_file() {
+        let _ = request.respond(tiny_http::Response::from_string("Not Found").with_status_code(404));
+        return;
+    }
+
+    let content_type_header = tiny_http::Header::from_bytes(&b"Content-Type"[..], content_type_for(&resolved).as_bytes()).unwrap();
+    let file_size = std::fs::metadata(&resolved).map(|m| m.len()).unwrap_or(0);
+    let range = request.headers().iter()
+        .find(|h| h.field.as_str().as_str().eq_ignore_ascii_case("range"))
+        .and_then(|h| parse_range(h.value.as_str(), file_size));
+
+    if let Some((start, end)) = range {
+        let mut file = match std::fs::File::open(&resolved) {
+            Ok(f) => f,
+            Err(_) => { let _ = request.respond(tiny_http::Response::from_string("Not Found").with_status_code(404)); return; }
+        };
+        if file.seek(SeekFrom::Start(start)).is_err() {
+            let _ = request.respond(tiny_http::Response::from_string("Internal Server Error").with_status_code(500));
+            return;
+        }
+        let mut buf = vec![0u8; (end - start + 1) as usize];
+        if file.read_exact(&mut buf).is_err() {
+            let _ = request.respond(tiny_http::Response::from_string("Internal Server Error").with_status_code(500));
+            return;
+        }
+        let content_range = tiny_http::Header::from_bytes(&b"Content-Range"[..], format!("bytes {}-{}/{}", start, end, file_size).as_bytes()).unwrap();
+        let response = tiny_http::Response::from_data(buf)
+            .with_status_code(206)
+            .with_header(content_type_header)
+            .with_header(content_range);
+        let _ = request.respond(response);
+        return;
+    }
+
+    match std::fs::File::open(&resolved) {
+        Ok(file) => { let _ = request.respond(tiny_http::Response::from_file(file).with_header(content_type_header)); },
+        Err(_) => { let _ = request.respond(tiny_http::Response::from_string("Not Found").with_status_code(404)); },
+    }
+}
+
+/// Serve a directory over plain HTTP on a background thread using
+/// `tiny_http`, so the crate can serve files locally (e.g. for a vision
+/// model or browser to fetch) and not just download them. Binding `port=0`
+/// lets the OS pick a free port, which is reported back in the result. The
+/// server is registered in `servers()` under its bound port so `stop` can
+/// shut it down later.
+fn rust_serve_directory_impl(dir: &str, host: &str, port: u16, read_only: bool) -> serde_json::Value {
+    let root = match std::fs::canonicalize(dir) {
+        Ok(p) => p,
+        Err(e) => return json!({"success": false, "result": serde_json::Value::Null, "error": format!("Invalid directory: {}", e)}),
+    };
+
+    let server = match tiny_http::Server::http(format!("{}:{}", host, port)) {
+        Ok(s) => s,
+        Err(e) => return json!({"success": false, "result": serde_json::Value::Null, "error": format!("Failed to bind server: {}", e)}),
+    };
+
+    let actual_port = server.server_addr().to_ip().map(|addr| addr.port()).unwrap_or(port);
+    let serve_root = root.clone();
+    let stop_flag = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+    servers().lock().unwrap().insert(actual_port, stop_flag.clone());
+
+    std::thread::spawn(move || {
+        loop {
+            if stop_flag.load(std::sync::atomic::Ordering::Relaxed) {
+                break;
+            }
+            match server.recv_timeout(std::time::Duration::from_millis(200)) {
+                Ok(Some(request)) => handle_serve_request(request, &serve_root, read_only),
+                Ok(None) => continue,
+                Err(_) => break,
+            }
+        }
+        servers().lock().unwrap().remove(&actual_port);
+    });
+
+    json!({
+        "success": true,
+        "result": {
+            "directory": root.to_string_lossy(),
+            "host": host,
+            "port": actual_port,
+            "url": format!("http://{}:{}/", host, actual_port),
+            "read_only": read_only,
+            "method": "rust_tiny_http"
+        },
+        "error": null
+    })
+}
+
+/// Signal the `serve` background thread bound to `port` to stop. The thread
+/// notices on its next poll (at most 200ms later) and removes itself from
+/// the registry; this function removes it immediately so a subsequent
+/// `stop` call on the same port reports it as already gone.
+fn rust_stop_server_impl(port: u16) -> serde_json::Value {
+    match servers().lock().unwrap().remove(&port) {
+        Some(stop_flag) => {
+            stop_flag.store(true, std::sync::atomic::Ordering::Relaxed);
+            json!({"success": true, "result": {"port": port, "action": "stopped"}, "error": null})
+        },
+        None => json!({"success": false, "result": serde_json::Value::Null, "error": format!("No server running on port {}", port)}),
+    }
+}
+
 fn rust_get_public_ip_impl() -> serde_json::Value {
     let ip_services = vec![
         "https://api.ipify.org",
@@ -416,14 +1044,58 @@ fn rust_get_public_ip_impl() -> serde_json::Value {
     })
 }
 
+/// Normalize a user-supplied URL, adding `https://` when no scheme is
+/// present, using RFC 3986 parsing via the `url` crate rather than a plain
+/// string-prefix check.
+fn normalize_url(input: &str) -> Result<String, String> {
+    match url::Url::parse(input) {
+        Ok(parsed) => Ok(parsed.to_string()),
+        Err(url::ParseError::RelativeUrlWithoutBase) => {
+            url::Url::parse(&format!("https://{}", input))
+                .map(|parsed| parsed.to_string())
+                .map_err(|e| format!("Invalid URL: {}", e))
+        },
+        Err(e) => Err(format!("Invalid URL: {}", e)),
+    }
+}
+
+/// Decompose `url` into its scheme/host/port/path/query-pair map without
+/// opening it, rejecting malformed input instead of silently guessing a
+/// scheme (unlike `normalize_url`, which is meant for user-typed targets).
+fn rust_parse_url_impl(url: &str) -> serde_json::Value {
+    let parsed = match url::Url::parse(url) {
+        Ok(p) => p,
+        Err(e) => return json!({"success": false, "result": serde_json::Value::Null, "error": format!("Invalid URL: {}", e)}),
+    };
+
+    let query_pairs: std::collections::HashMap<String, String> = parsed
+        .query_pairs()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+    json!({
+        "success": true,
+        "result": {
+            "url": parsed.as_str(),
+            "scheme": parsed.scheme(),
+            "host": parsed.host_str(),
+            "port": parsed.port_or_known_default(),
+            "path": parsed.path(),
+            "query": parsed.query(),
+            "query_pairs": query_pairs,
+            "fragment": parsed.fragment(),
+            "method": "rust_url"
+        },
+        "error": null
+    })
+}
+
 fn rust_open_website_impl(url: &str) -> serde_json::Value {
-    // Add protocol if missing
-    let final_url = if url.starts_with("http://") || url.starts_with("https://") {
-        url.to_string()
-    } else {
-        format!("https://{}", url)
+    let final_url = match normalize_url(url) {
+        Ok(u) => u,
+        Err(e) => return json!({"success": false, "result": serde_json::Value::Null, "error": e}),
     };
-    
+
     // Use platform-specific commands to open URL
     let result = if cfg!(target_os = "windows") {
         Command::new("cmd")
@@ -474,12 +1146,11 @@ fn rust_open_website_impl(url: &str) -> serde_json::Value {
 fn rust_navigate_browser_impl(url: &str, browser: &str) -> serde_json::Value {
     // For now, just use the same implementation as open_website
     // In the future, could add browser-specific commands
-    let final_url = if url.starts_with("http://") || url.starts_with("https://") {
-        url.to_string()
-    } else {
-        format!("https://{}", url)
+    let final_url = match normalize_url(url) {
+        Ok(u) => u,
+        Err(e) => return json!({"success": false, "result": serde_json::Value::Null, "error": e}),
     };
-    
+
     let result = rust_open_website_impl(&final_url);
     
     // Modify result to include browser info
@@ -495,14 +1166,19 @@ fn rust_navigate_browser_impl(url: &str, browser: &str) -> serde_json::Value {
 }
 
 fn rust_search_browser_impl(query: &str, search_engine: &str) -> serde_json::Value {
-    // Build search URL
-    let search_url = match search_engine {
-        "google" => format!("https://www.google.com/search?q={}", urlencoding::encode(query)),
-        "bing" => format!("https://www.bing.com/search?q={}", urlencoding::encode(query)),
-        "duckduckgo" => format!("https://duckduckgo.com/?q={}", urlencoding::encode(query)),
-        _ => format!("https://www.google.com/search?q={}", urlencoding::encode(query)),
+    // Build the search URL with RFC-compliant query encoding via `url`'s
+    // query_pairs_mut, instead of a hand-rolled percent-encoder.
+    let base = match search_engine {
+        "bing" => "https://www.bing.com/search",
+        "duckduckgo" => "https://duckduckgo.com/",
+        _ => "https://www.google.com/search",
     };
-    
+
+    let mut search_url = url::Url::parse(base).expect("hard-coded search engine URLs are valid");
+    search_url.query_pairs_mut().append_pair("q", query);
+    let search_url = search_url.to_string();
+
+
     let result = rust_open_website_impl(&search_url);
     
     // Modify result to include search info
@@ -533,17 +1209,3 @@ fn rust_open_browser_impl(url: &str, browser: &str) -> serde_json::Value {
         result
     }
 }
-
-// URL encoding helper (simple implementation)
-mod urlencoding {
-    pub fn encode(input: &str) -> String {
-        input
-            .chars()
-            .map(|c| match c {
-                'A'..='Z' | 'a'..='z' | '0'..='9' | '-' | '_' | '.' | '~' => c.to_string(),
-                ' ' => "+".to_string(),
-                _ => format!("%{:02X}", c as u8),
-            })
-            .collect()
-    }
-}
@@ -6,67 +6,293 @@ use serde_json::json;
 use std::process::Command;
 use std::env;
 use std::path::Path;
+use sysinfo::System;
+
+/// Stable error categories for shell/process operations, so callers can
+/// branch on `error.code` instead of pattern-matching message strings.
+/// Free-form subprocess stderr (the actual command's own output) is left as
+/// a plain string in `result.stderr` - this taxonomy only covers errors
+/// *this module* raises about the call itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ShellErrorKind {
+    InvalidArgument,
+    DangerousCommand,
+    UnknownAction,
+    NotFound,
+    PermissionDenied,
+    Timeout,
+    Io,
+    /// The subprocess ran to completion but exited with a non-zero status.
+    NonZeroExit { code: i32 },
+}
+
+impl ShellErrorKind {
+    fn kind(&self) -> &'static str {
+        match self {
+            ShellErrorKind::InvalidArgument => "invalid_argument",
+            ShellErrorKind::DangerousCommand => "dangerous_command",
+            ShellErrorKind::UnknownAction => "unknown_action",
+            ShellErrorKind::NotFound => "not_found",
+            ShellErrorKind::PermissionDenied => "permission_denied",
+            ShellErrorKind::Timeout => "timeout",
+            ShellErrorKind::Io => "io_error",
+            ShellErrorKind::NonZeroExit { .. } => "non_zero_exit",
+        }
+    }
+
+    fn exit_code(&self) -> Option<i32> {
+        match self {
+            ShellErrorKind::NonZeroExit { code } => Some(*code),
+            _ => None,
+        }
+    }
+}
+
+/// Classify a subprocess that ran to completion but failed, from its stderr
+/// and exit code - distinguishes a permission problem from an ordinary
+/// non-zero exit so callers don't have to grep `stderr` themselves.
+fn classify_process_failure(stderr: &str, code: Option<i32>) -> ShellErrorKind {
+    let lower = stderr.to_lowercase();
+    if lower.contains("permission denied") || lower.contains("not permitted") {
+        ShellErrorKind::PermissionDenied
+    } else {
+        ShellErrorKind::NonZeroExit { code: code.unwrap_or(-1) }
+    }
+}
+
+/// Build the standard `{success, result, error}` envelope for a failure.
+/// `error` stays a plain human-readable string (matching the rest of the
+/// crate's error shape), with a stable `error_kind` and, for
+/// [`ShellErrorKind::NonZeroExit`], the numeric `exit_code` alongside it so
+/// callers can branch without re-parsing the message.
+fn shell_error(kind: ShellErrorKind, message: impl Into<String>) -> serde_json::Value {
+    json!({
+        "success": false,
+        "result": serde_json::Value::Null,
+        "error": message.into(),
+        "error_kind": kind.kind(),
+        "exit_code": kind.exit_code()
+    })
+}
+
+/// What a policy match does to the command: `enforce` blocks it, `warn`
+/// allows it through but flags the match in the response, and `audit` lets
+/// an operator stage a stricter policy (e.g. a new regex) without yet
+/// breaking anything.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PolicyMode {
+    Audit,
+    Warn,
+    Enforce,
+}
+
+impl PolicyMode {
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "audit" => Some(PolicyMode::Audit),
+            "warn" => Some(PolicyMode::Warn),
+            "enforce" => Some(PolicyMode::Enforce),
+            _ => None,
+        }
+    }
+}
+
+/// Outcome of running a command through [`CommandPolicy::check`].
+struct PolicyCheck {
+    matched: Option<String>,
+    mode: PolicyMode,
+}
+
+impl PolicyCheck {
+    fn blocks(&self) -> bool {
+        self.matched.is_some() && self.mode == PolicyMode::Enforce
+    }
+
+    fn warning(&self) -> Option<&str> {
+        (self.matched.is_some() && self.mode != PolicyMode::Enforce).then(|| self.matched.as_deref()).flatten()
+    }
+}
+
+/// Command-safety policy for `rust_shell_execute("run", ...)`. Checks, in
+/// order: an optional allowlist of executables (if set, the command's
+/// leading binary must be in it), a deny-list of substrings, and a deny-list
+/// of regex patterns. The command is normalized first - whitespace collapsed
+/// and the leading binary resolved to its basename - so `rm  -rf /` (double
+/// space) or `/bin/rm -rf /` can't slip past a check written against `rm -rf /`.
+///
+/// Configurable via a JSON file at the path in `QUE_SHELL_POLICY_FILE`,
+/// shaped like
+/// `{"deny_substrings": ["rm -rf /"], "deny_patterns": ["mkfs\\.\\w+"], "allow_binaries": ["ls", "cat"], "mode": "warn"}`
+/// - falls back to the built-in denylist and `enforce` mode when the env var
+/// is unset or the file can't be read/parsed.
+struct CommandPolicy {
+    deny_substrings: Vec<String>,
+    deny_patterns: Vec<regex::Regex>,
+    allow_binaries: Option<Vec<String>>,
+    mode: PolicyMode,
+}
+
+impl CommandPolicy {
+    fn built_in_denylist() -> Vec<String> {
+        ["rm -rf /", "dd if=", "mkfs", "fdisk", "format", "sudo rm -rf"]
+            .into_iter()
+            .map(String::from)
+            .collect()
+    }
+
+    fn load() -> Self {
+        let configured: Option<serde_json::Value> = env::var("QUE_SHELL_POLICY_FILE").ok().and_then(|path| {
+            let contents = std::fs::read_to_string(path).ok()?;
+            serde_json::from_str(&contents).ok()
+        });
+
+        let deny_substrings = configured
+            .as_ref()
+            .and_then(|p| p.get("deny_substrings"))
+            .and_then(|v| v.as_array())
+            .map(|list| list.iter().filter_map(|v| v.as_str().map(String::from)).collect::<Vec<_>>())
+            .unwrap_or_else(Self::built_in_denylist);
+
+        let deny_patterns = configured
+            .as_ref()
+            .and_then(|p| p.get("deny_patterns"))
+            .and_then(|v| v.as_array())
+            .map(|list| {
+                list.iter()
+                    .filter_map(|v| v.as_str())
+                    .filter_map(|s| regex::Regex::new(s).ok())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        let allow_binaries = configured
+            .as_ref()
+            .and_then(|p| p.get("allow_binaries"))
+            .and_then(|v| v.as_array())
+            .map(|list| list.iter().filter_map(|v| v.as_str().map(|s| s.to_lowercase())).collect::<Vec<_>>());
+
+        let mode = configured
+            .as_ref()
+            .and_then(|p| p.get("mode"))
+            .and_then(|v| v.as_str())
+            .and_then(PolicyMode::parse)
+            .unwrap_or(PolicyMode::Enforce);
+
+        Self { deny_substrings, deny_patterns, allow_binaries, mode }
+    }
+
+    /// Collapse repeated whitespace, lowercase, and strip any directory from
+    /// the leading binary, so a check written against `"rm -rf /"` still
+    /// matches `"rm  -rf   /"` and `"/bin/rm -rf /"`.
+    fn normalize(command: &str) -> String {
+        let collapsed = command.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+        match collapsed.split_once(' ') {
+            Some((first, rest)) => format!("{} {}", Self::leading_binary(first), rest),
+            None => Self::leading_binary(&collapsed).to_string(),
+        }
+    }
+
+    /// The leading executable of a normalized command, stripped of any
+    /// directory component (`/usr/bin/rm` and `rm` resolve the same).
+    fn leading_binary(normalized: &str) -> &str {
+        let token = normalized.split(' ').next().unwrap_or("");
+        token.rsplit('/').next().unwrap_or(token)
+    }
+
+    /// Checks `command` against the allowlist, then the substring and regex
+    /// denylists, returning the first match (if any) alongside the
+    /// configured mode so the caller can decide whether to block or warn.
+    fn check(&self, command: &str) -> PolicyCheck {
+        let normalized = Self::normalize(command);
+
+        if let Some(allow) = &self.allow_binaries {
+            let binary = Self::leading_binary(&normalized);
+            if !allow.iter().any(|b| b == binary) {
+                return PolicyCheck {
+                    matched: Some(format!("binary \"{}\" is not in the allowlist", binary)),
+                    mode: self.mode,
+                };
+            }
+        }
+
+        if let Some(denied) = self.deny_substrings.iter().find(|denied| normalized.contains(denied.to_lowercase().as_str())) {
+            return PolicyCheck { matched: Some(denied.clone()), mode: self.mode };
+        }
+
+        if let Some(pattern) = self.deny_patterns.iter().find(|p| p.is_match(&normalized)) {
+            return PolicyCheck { matched: Some(format!("pattern /{}/", pattern.as_str())), mode: self.mode };
+        }
+
+        PolicyCheck { matched: None, mode: self.mode }
+    }
+}
 
 /// Universal shell executor - consolidated command operations
+///
+/// `run` accepts `stdin` (piped to the child's standard input) and
+/// `timeout_secs` (the child is killed and `timed_out: true` is reported if
+/// it doesn't finish in time, instead of blocking forever).
+///
+/// `kill` sends a graceful signal (SIGTERM / WM_CLOSE) first and only
+/// escalates to a forceful one (SIGKILL / TerminateProcess) if the process
+/// is still alive after `grace_secs` (default 3), reporting which signal
+/// actually stopped it. Pass `signal` (e.g. `"SIGHUP"`, `"INT"`, or a raw
+/// number) to send exactly that signal instead, with no escalation.
 #[pyfunction]
-pub fn rust_shell_execute(action: String, command: Option<String>, package: Option<String>, pid: Option<u32>, program: Option<String>) -> PyResult<String> {
+#[pyo3(signature = (action, command=None, package=None, pid=None, program=None, stdin=None, timeout_secs=None, grace_secs=None, signal=None, filter=None, sort_by=None, raw_output=None, stream=None, on_line=None))]
+pub fn rust_shell_execute(
+    action: String,
+    command: Option<String>,
+    package: Option<String>,
+    pid: Option<u32>,
+    program: Option<String>,
+    stdin: Option<String>,
+    timeout_secs: Option<u64>,
+    grace_secs: Option<u64>,
+    signal: Option<String>,
+    filter: Option<String>,
+    sort_by: Option<String>,
+    raw_output: Option<bool>,
+    stream: Option<bool>,
+    on_line: Option<Py<PyAny>>,
+) -> PyResult<String> {
     let result = match action.as_str() {
         "run" => {
             let cmd = command.unwrap_or_default();
             if cmd.is_empty() {
-                json!({
-                    "success": false,
-                    "result": serde_json::Value::Null,
-                    "error": "Missing required argument: command"
-                })
+                shell_error(ShellErrorKind::InvalidArgument, "Missing required argument: command")
             } else {
-                rust_run_command_impl(&cmd)
+                rust_run_command_impl(&cmd, stdin.as_deref(), timeout_secs.unwrap_or(30), stream.unwrap_or(false), on_line)
             }
         },
         "install" => {
             let pkg = package.unwrap_or_default();
             if pkg.is_empty() {
-                json!({
-                    "success": false,
-                    "result": serde_json::Value::Null,
-                    "error": "Missing required argument: package"
-                })
+                shell_error(ShellErrorKind::InvalidArgument, "Missing required argument: package")
             } else {
                 rust_install_package_impl(&pkg)
             }
         },
         "kill" => {
             if let Some(process_pid) = pid {
-                rust_kill_process_impl(process_pid)
+                rust_kill_process_impl(process_pid, grace_secs.unwrap_or(3), signal.as_deref())
             } else {
-                json!({
-                    "success": false,
-                    "result": serde_json::Value::Null,
-                    "error": "Missing required argument: pid"
-                })
+                shell_error(ShellErrorKind::InvalidArgument, "Missing required argument: pid")
             }
         },
         "which" => {
             let prog = program.unwrap_or_default();
             if prog.is_empty() {
-                json!({
-                    "success": false,
-                    "result": serde_json::Value::Null,
-                    "error": "Missing required argument: program"
-                })
+                shell_error(ShellErrorKind::InvalidArgument, "Missing required argument: program")
             } else {
                 rust_which_command_impl(&prog)
             }
         },
         "ps" => {
-            rust_list_processes_impl()
+            rust_list_processes_impl(filter.as_deref(), sort_by.as_deref(), raw_output.unwrap_or(false))
         },
         _ => {
-            json!({
-                "success": false,
-                "result": serde_json::Value::Null,
-                "error": format!("Unknown action: {}. Use: run, install, kill, which, ps", action)
-            })
+            shell_error(ShellErrorKind::UnknownAction, format!("Unknown action: {}. Use: run, install, kill, which, ps", action))
         }
     };
     
@@ -74,17 +300,19 @@ pub fn rust_shell_execute(action: String, command: Option<String>, package: Opti
 }
 
 /// Development environment manager - consolidated environment operations
+///
+/// `doctor` reports installed toolchain versions
+/// (rustc/cargo/python3/node/git/pip/python), whether each is on PATH, the
+/// detected package manager, and, for each project manifest present at
+/// `path` (Cargo.toml, Cargo.lock, package.json, pyproject.toml), its
+/// declared version and/or dependencies.
 #[pyfunction]
 pub fn rust_environment_manager(action: String, key: Option<String>, value: Option<String>, path: Option<String>) -> PyResult<String> {
     let result = match action.as_str() {
         "get_env" => {
             let env_key = key.unwrap_or_default();
             if env_key.is_empty() {
-                json!({
-                    "success": false,
-                    "result": serde_json::Value::Null,
-                    "error": "Missing required argument: key"
-                })
+                shell_error(ShellErrorKind::InvalidArgument, "Missing required argument: key")
             } else {
                 rust_get_env_var_impl(&env_key)
             }
@@ -92,11 +320,7 @@ pub fn rust_environment_manager(action: String, key: Option<String>, value: Opti
         "set_env" => {
             let env_key = key.unwrap_or_default();
             if env_key.is_empty() {
-                json!({
-                    "success": false,
-                    "result": serde_json::Value::Null,
-                    "error": "Missing required argument: key"
-                })
+                shell_error(ShellErrorKind::InvalidArgument, "Missing required argument: key")
             } else {
                 rust_set_env_var_impl(&env_key, value.as_deref())
             }
@@ -110,11 +334,7 @@ pub fn rust_environment_manager(action: String, key: Option<String>, value: Opti
         "change_dir" => {
             let dir_path = path.unwrap_or_default();
             if dir_path.is_empty() {
-                json!({
-                    "success": false,
-                    "result": serde_json::Value::Null,
-                    "error": "Missing required argument: path"
-                })
+                shell_error(ShellErrorKind::InvalidArgument, "Missing required argument: path")
             } else {
                 rust_change_directory_impl(&dir_path)
             }
@@ -122,84 +342,265 @@ pub fn rust_environment_manager(action: String, key: Option<String>, value: Opti
         "create_venv" => {
             let venv_name = key.unwrap_or_default(); // Using key as name for venv
             if venv_name.is_empty() {
-                json!({
-                    "success": false,
-                    "result": serde_json::Value::Null,
-                    "error": "Missing required argument: name (use key parameter)"
-                })
+                shell_error(ShellErrorKind::InvalidArgument, "Missing required argument: name (use key parameter)")
             } else {
                 rust_create_virtual_env_impl(&venv_name, path.as_deref())
             }
         },
+        "doctor" => {
+            rust_environment_doctor_impl(path.as_deref())
+        },
         _ => {
-            json!({
-                "success": false,
-                "result": serde_json::Value::Null,
-                "error": format!("Unknown action: {}. Use: get_env, set_env, list_env, get_cwd, change_dir, create_venv", action)
-            })
+            shell_error(ShellErrorKind::UnknownAction, format!("Unknown action: {}. Use: get_env, set_env, list_env, get_cwd, change_dir, create_venv, doctor", action))
         }
     };
-    
+
     Ok(result.to_string())
 }
 
-// Implementation helpers
-fn rust_run_command_impl(command: &str) -> serde_json::Value {
-    // Security: Basic command validation
-    let dangerous_commands = ["rm -rf /", "dd if=", "mkfs", "fdisk", "format", "sudo rm -rf"];
-    for dangerous in &dangerous_commands {
-        if command.to_lowercase().contains(dangerous) {
-            return json!({
-                "success": false,
-                "result": serde_json::Value::Null,
-                "error": "Dangerous command blocked for safety"
-            });
+/// Reads a pipe to completion, line by line. When `on_line` is set, each line
+/// (sans trailing newline) is handed to the Python callback as it arrives
+/// alongside which stream it came from, so long-running commands can stream
+/// progress instead of waiting for the whole buffer.
+fn drain_pipe_with_callback(
+    pipe: impl std::io::Read,
+    stream_name: &'static str,
+    on_line: Option<&Py<PyAny>>,
+) -> Vec<u8> {
+    use std::io::BufRead;
+
+    let mut buf = Vec::new();
+    let mut reader = std::io::BufReader::new(pipe);
+    let mut line = String::new();
+    loop {
+        line.clear();
+        match reader.read_line(&mut line) {
+            Ok(0) => break,
+            Ok(_) => {
+                if let Some(callback) = on_line {
+                    Python::with_gil(|py| {
+                        let _ = callback.call1(py, (line.trim_end_matches('\n'), stream_name));
+                    });
+                }
+                buf.extend_from_slice(line.as_bytes());
+            }
+            Err(_) => break,
         }
     }
-    
-    // Execute command based on platform
-    let output = if cfg!(target_os = "windows") {
-        Command::new("cmd")
-            .args(["/C", command])
-            .output()
+    buf
+}
+
+// Implementation helpers
+fn rust_run_command_impl(
+    command: &str,
+    stdin: Option<&str>,
+    timeout_secs: u64,
+    stream: bool,
+    on_line: Option<Py<PyAny>>,
+) -> serde_json::Value {
+    use std::process::Stdio;
+    use std::time::Duration;
+    use wait_timeout::ChildExt;
+
+    // Security: reject (or flag, depending on the configured mode) commands
+    // the safety policy matches.
+    let policy_check = CommandPolicy::load().check(command);
+    if policy_check.blocks() {
+        return shell_error(
+            ShellErrorKind::DangerousCommand,
+            format!("Command blocked by safety policy (matched {})", policy_check.matched.unwrap_or_default()),
+        );
+    }
+    let policy_warning = policy_check.warning().map(String::from);
+
+    // Spawn instead of `.output()` so we can pipe stdin and enforce a timeout
+    // without blocking forever on a hung child.
+    let mut cmd = if cfg!(target_os = "windows") {
+        let mut c = Command::new("cmd");
+        c.args(["/C", command]);
+        c
     } else {
-        Command::new("sh")
-            .arg("-c")
-            .arg(command)
-            .output()
+        let mut c = Command::new("sh");
+        c.arg("-c").arg(command);
+        c
     };
-    
-    match output {
-        Ok(result) => {
-            let stdout = String::from_utf8_lossy(&result.stdout);
-            let stderr = String::from_utf8_lossy(&result.stderr);
-            let success = result.status.success();
-            
+    cmd.stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped());
+
+    let mut child = match cmd.spawn() {
+        Ok(c) => c,
+        Err(e) => {
+            return shell_error(ShellErrorKind::Io, format!("Failed to execute command: {}", e));
+        }
+    };
+
+    // Write stdin on its own thread: a large payload combined with a child
+    // that starts writing to stdout/stderr before it has read all of stdin
+    // would otherwise deadlock against the reader threads below.
+    let stdin_pipe = child.stdin.take();
+    let stdin_payload = stdin.map(str::to_string);
+    let stdin_writer = std::thread::spawn(move || {
+        if let Some(mut pipe) = stdin_pipe {
+            if let Some(input) = stdin_payload {
+                use std::io::Write;
+                let _ = pipe.write_all(input.as_bytes());
+            }
+            // `pipe` drops here, closing our end so the child sees EOF.
+        }
+    });
+
+    // Read stdout/stderr on separate threads so a chatty child can't fill one
+    // pipe's buffer and deadlock while we wait on the other.
+    let stdout_pipe = child.stdout.take();
+    let stderr_pipe = child.stderr.take();
+    let stdout_callback = if stream { on_line.clone() } else { None };
+    let stderr_callback = if stream { on_line } else { None };
+    let stdout_reader = std::thread::spawn(move || {
+        stdout_pipe.map(|p| drain_pipe_with_callback(p, "stdout", stdout_callback.as_ref())).unwrap_or_default()
+    });
+    let stderr_reader = std::thread::spawn(move || {
+        stderr_pipe.map(|p| drain_pipe_with_callback(p, "stderr", stderr_callback.as_ref())).unwrap_or_default()
+    });
+
+    let wait_result = child.wait_timeout(Duration::from_secs(timeout_secs));
+
+    match wait_result {
+        Ok(Some(status)) => {
+            let _ = stdin_writer.join();
+            let stdout = String::from_utf8_lossy(&stdout_reader.join().unwrap_or_default()).to_string();
+            let stderr = String::from_utf8_lossy(&stderr_reader.join().unwrap_or_default()).to_string();
+            let success = status.success();
+
+            let error_kind = (!success).then(|| classify_process_failure(&stderr, status.code()));
+
             json!({
                 "success": success,
                 "result": {
                     "command": command,
-                    "return_code": result.status.code().unwrap_or(-1),
+                    "return_code": status.code().unwrap_or(-1),
                     "stdout": stdout,
                     "stderr": stderr,
+                    "timed_out": false,
+                    "policy_warning": policy_warning,
                     "method": "rust_shell_execute"
                 },
-                "error": if success { serde_json::Value::Null } else { json!(stderr) }
+                "error": if success { serde_json::Value::Null } else { json!(stderr) },
+                "error_kind": error_kind.map(|k| k.kind()),
+                "exit_code": error_kind.and_then(|k| k.exit_code())
             })
         },
-        Err(e) => {
+        Ok(None) => {
+            // Timed out - kill the child so it doesn't keep running in the background.
+            let _ = child.kill();
+            let _ = child.wait();
+            let _ = stdin_writer.join();
+            let partial_stdout = String::from_utf8_lossy(&stdout_reader.join().unwrap_or_default()).to_string();
+            let partial_stderr = String::from_utf8_lossy(&stderr_reader.join().unwrap_or_default()).to_string();
+
             json!({
                 "success": false,
-                "result": serde_json::Value::Null,
-                "error": format!("Failed to execute command: {}", e)
+                "result": {
+                    "command": command,
+                    "return_code": serde_json::Value::Null,
+                    "partial_stdout": partial_stdout,
+                    "partial_stderr": partial_stderr,
+                    "timed_out": true,
+                    "policy_warning": policy_warning,
+                    "method": "rust_shell_execute"
+                },
+                "error": format!("Command timed out after {} seconds", timeout_secs),
+                "error_kind": ShellErrorKind::Timeout.kind(),
+                "exit_code": serde_json::Value::Null
             })
+        },
+        Err(e) => {
+            shell_error(ShellErrorKind::Io, format!("Failed to wait for command: {}", e))
         }
     }
 }
 
-fn rust_install_package_impl(package: &str) -> serde_json::Value {
-    // Determine package manager based on platform and available tools
-    let package_manager = if cfg!(target_os = "windows") {
+/// Builder for a single command invocation, optionally routed through `sudo`.
+/// Lets call sites assemble program/args without hand-formatting shell
+/// strings, and keeps the "does this need sudo" decision in one place.
+pub(crate) struct ShellCommand {
+    program: String,
+    args: Vec<String>,
+    sudo: bool,
+}
+
+impl ShellCommand {
+    pub(crate) fn new(program: impl Into<String>) -> Self {
+        Self { program: program.into(), args: Vec::new(), sudo: false }
+    }
+
+    pub(crate) fn args(mut self, args: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Route this command through `sudo -n` so it can ride an active
+    /// [`SudoLoop`] instead of prompting for a password per call.
+    pub(crate) fn sudo(mut self) -> Self {
+        self.sudo = true;
+        self
+    }
+
+    pub(crate) fn display(&self) -> String {
+        let prefix = if self.sudo && !cfg!(target_os = "windows") { "sudo " } else { "" };
+        format!("{}{} {}", prefix, self.program, self.args.join(" "))
+    }
+
+    pub(crate) fn run(&self) -> std::io::Result<std::process::Output> {
+        if self.sudo && !cfg!(target_os = "windows") {
+            Command::new("sudo").arg("-n").arg(&self.program).args(&self.args).output()
+        } else {
+            Command::new(&self.program).args(&self.args).output()
+        }
+    }
+}
+
+/// Keeps a cached `sudo` credential alive on a background thread (`sudo -v`
+/// every minute) so a sequence of privileged `sudo -n` installs keeps running
+/// on one cached credential instead of failing once it expires.
+/// Dropping the loop stops the refresh thread; it does not revoke the cache.
+pub(crate) struct SudoLoop {
+    stop: std::sync::Arc<std::sync::atomic::AtomicBool>,
+}
+
+impl SudoLoop {
+    /// Starts the refresh loop if a sudo credential is already cached
+    /// (`sudo -n -v` succeeds without a prompt). Returns `None` on Windows,
+    /// where this doesn't apply, or if no cached credential is available -
+    /// the `sudo -n` commands then fail instead of prompting.
+    pub(crate) fn start() -> Option<Self> {
+        if cfg!(target_os = "windows") {
+            return None;
+        }
+        if !Command::new("sudo").args(["-n", "-v"]).output().map(|o| o.status.success()).unwrap_or(false) {
+            return None;
+        }
+
+        let stop = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let stop_clone = stop.clone();
+        std::thread::spawn(move || {
+            while !stop_clone.load(std::sync::atomic::Ordering::Relaxed) {
+                let _ = Command::new("sudo").args(["-n", "-v"]).output();
+                std::thread::sleep(std::time::Duration::from_secs(60));
+            }
+        });
+        Some(Self { stop })
+    }
+}
+
+impl Drop for SudoLoop {
+    fn drop(&mut self) {
+        self.stop.store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+}
+
+/// Picks the system package manager to drive for `rust_install_package_impl`
+/// and to report in `rust_environment_doctor_impl`'s results.
+fn detect_package_manager() -> &'static str {
+    if cfg!(target_os = "windows") {
         "pip"
     } else if cfg!(target_os = "macos") {
         "brew"
@@ -216,37 +617,67 @@ fn rust_install_package_impl(package: &str) -> serde_json::Value {
         } else {
             "pip"
         }
-    };
-    
-    // Build install command
-    let command = match package_manager {
-        "apt" => format!("sudo apt update && sudo apt install -y {}", package),
-        "yum" => format!("sudo yum install -y {}", package),
-        "dnf" => format!("sudo dnf install -y {}", package),
-        "pacman" => format!("sudo pacman -S --noconfirm {}", package),
-        "brew" => format!("brew install {}", package),
-        "pip" => format!("pip install {}", package),
-        _ => format!("pip install {}", package),
-    };
-    
-    // Execute install command
-    let output = if cfg!(target_os = "windows") {
-        Command::new("cmd")
-            .args(["/C", &command])
-            .output()
+    }
+}
+
+/// Refuses privileged actions (package installs) when this process itself is
+/// already running as root, unless `QUE_ALLOW_ROOT` is set. `SudoLoop`
+/// escalates an unprivileged process for just the commands that need it; if
+/// the whole tool is already root, that escalation boundary is gone and a
+/// buggy/compromised caller could run arbitrary privileged commands with no
+/// sudo prompt at all - this is the companion guard that keeps root a
+/// deliberate, opt-in choice rather than an ambient default.
+#[cfg(not(target_os = "windows"))]
+fn refuse_if_running_as_root() -> Option<serde_json::Value> {
+    if unsafe { libc::geteuid() } == 0 && env::var("QUE_ALLOW_ROOT").is_err() {
+        Some(shell_error(
+            ShellErrorKind::PermissionDenied,
+            "Refusing to run a privileged action while this process is running as root (euid 0). Set QUE_ALLOW_ROOT=1 to override.",
+        ))
     } else {
-        Command::new("sh")
-            .arg("-c")
-            .arg(&command)
-            .output()
+        None
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn refuse_if_running_as_root() -> Option<serde_json::Value> {
+    None
+}
+
+fn rust_install_package_impl(package: &str) -> serde_json::Value {
+    if let Some(refusal) = refuse_if_running_as_root() {
+        return refusal;
+    }
+
+    let package_manager = detect_package_manager();
+
+    let needs_sudo = matches!(package_manager, "apt" | "yum" | "dnf" | "pacman");
+    // Keep the sudo timestamp fresh across the (potentially slow) update +
+    // install pair below, so the install's `sudo -n` doesn't fail on a
+    // credential that expired during the update.
+    let _sudo_loop = if needs_sudo { SudoLoop::start() } else { None };
+
+    if package_manager == "apt" {
+        let _ = ShellCommand::new("apt").args(["update"]).sudo().run();
+    }
+
+    let install = match package_manager {
+        "apt" => ShellCommand::new("apt").args(["install", "-y", package]).sudo(),
+        "yum" => ShellCommand::new("yum").args(["install", "-y", package]).sudo(),
+        "dnf" => ShellCommand::new("dnf").args(["install", "-y", package]).sudo(),
+        "pacman" => ShellCommand::new("pacman").args(["-S", "--noconfirm", package]).sudo(),
+        "brew" => ShellCommand::new("brew").args(["install", package]),
+        _ => ShellCommand::new("pip").args(["install", package]),
     };
-    
-    match output {
+    let command = install.display();
+
+    match install.run() {
         Ok(result) => {
             let stdout = String::from_utf8_lossy(&result.stdout);
             let stderr = String::from_utf8_lossy(&result.stderr);
             let success = result.status.success();
-            
+            let error_kind = (!success).then(|| classify_process_failure(&stderr, result.status.code()));
+
             json!({
                 "success": success,
                 "result": {
@@ -258,60 +689,151 @@ fn rust_install_package_impl(package: &str) -> serde_json::Value {
                     "stderr": stderr,
                     "method": "rust_package_install"
                 },
-                "error": if success { serde_json::Value::Null } else { json!(stderr) }
+                "error": if success { serde_json::Value::Null } else { json!(stderr) },
+                "error_kind": error_kind.map(|k| k.kind()),
+                "exit_code": error_kind.and_then(|k| k.exit_code())
             })
         },
-        Err(e) => {
-            json!({
-                "success": false,
-                "result": serde_json::Value::Null,
-                "error": format!("Failed to install package: {}", e)
-            })
-        }
+        Err(e) => shell_error(ShellErrorKind::Io, format!("Failed to install package: {}", e))
     }
 }
 
-fn rust_kill_process_impl(pid: u32) -> serde_json::Value {
-    // Use platform-specific process killing
-    let command = if cfg!(target_os = "windows") {
-        format!("taskkill /PID {} /F", pid)
+/// Returns true if a process with the given pid still exists, using the
+/// `kill(pid, 0)` signal-0 probe (no signal actually sent) instead of
+/// shelling out to the `kill` binary.
+#[cfg(not(target_os = "windows"))]
+fn process_alive(pid: u32) -> bool {
+    if unsafe { libc::kill(pid as libc::pid_t, 0) } == 0 {
+        return true;
+    }
+    // EPERM means the process exists but we can't signal it; any other
+    // errno (chiefly ESRCH) means it's gone.
+    std::io::Error::last_os_error().raw_os_error() != Some(libc::ESRCH)
+}
+
+/// Send a signal to `pid` natively via `libc::kill` rather than shelling out.
+#[cfg(not(target_os = "windows"))]
+fn send_signal(pid: u32, sig: i32) -> std::io::Result<()> {
+    if unsafe { libc::kill(pid as libc::pid_t, sig) } == 0 {
+        Ok(())
     } else {
-        format!("kill -TERM {}", pid)
-    };
-    
-    let output = if cfg!(target_os = "windows") {
-        Command::new("cmd")
-            .args(["/C", &command])
-            .output()
+        Err(std::io::Error::last_os_error())
+    }
+}
+
+/// Resolve a signal name (with or without the `SIG` prefix, case-insensitive)
+/// or a raw signal number into its libc value.
+#[cfg(not(target_os = "windows"))]
+fn parse_signal(name: &str) -> Option<i32> {
+    if let Ok(n) = name.trim().parse::<i32>() {
+        return Some(n);
+    }
+    let upper = name.trim().to_uppercase();
+    let bare = upper.strip_prefix("SIG").unwrap_or(&upper);
+    Some(match bare {
+        "HUP" => libc::SIGHUP,
+        "INT" => libc::SIGINT,
+        "QUIT" => libc::SIGQUIT,
+        "KILL" => libc::SIGKILL,
+        "TERM" => libc::SIGTERM,
+        "USR1" => libc::SIGUSR1,
+        "USR2" => libc::SIGUSR2,
+        "STOP" => libc::SIGSTOP,
+        "CONT" => libc::SIGCONT,
+        _ => return None,
+    })
+}
+
+/// Kill a process. With an explicit `signal` (name like `"SIGHUP"`/`"INT"` or
+/// a raw number), that single signal is sent and the result reported - no
+/// escalation, the caller chose exactly what to send. Without one, falls
+/// back to the default graceful-then-forceful escalation (SIGTERM/WM_CLOSE,
+/// then SIGKILL/TerminateProcess after `grace_secs`).
+fn rust_kill_process_impl(pid: u32, grace_secs: u64, signal: Option<&str>) -> serde_json::Value {
+    if cfg!(target_os = "windows") {
+        // Windows has no POSIX signal to pick between; the only caller
+        // choice that makes sense is "skip straight to force kill".
+        let force_only = matches!(signal.map(|s| s.to_uppercase()).as_deref(), Some("KILL") | Some("SIGKILL") | Some("9"));
+
+        if !force_only {
+            let _ = Command::new("cmd").args(["/C", &format!("taskkill /PID {}", pid)]).output();
+            std::thread::sleep(std::time::Duration::from_secs(grace_secs));
+            let still_running = Command::new("cmd")
+                .args(["/C", &format!("tasklist /FI \"PID eq {}\"", pid)])
+                .output()
+                .map(|o| String::from_utf8_lossy(&o.stdout).contains(&pid.to_string()))
+                .unwrap_or(false);
+
+            if !still_running {
+                return json!({
+                    "success": true,
+                    "result": {"pid": pid, "signal": "WM_CLOSE", "escalated": false, "exit_reason": "terminated"},
+                    "error": serde_json::Value::Null
+                });
+            }
+        }
+
+        let forced = Command::new("cmd").args(["/C", &format!("taskkill /PID {} /F", pid)]).output();
+        match forced {
+            Ok(result) if result.status.success() => json!({
+                "success": true,
+                "result": {"pid": pid, "signal": "TerminateProcess", "escalated": !force_only, "exit_reason": "killed"},
+                "error": serde_json::Value::Null
+            }),
+            Ok(result) => {
+                let stderr = String::from_utf8_lossy(&result.stderr).trim().to_string();
+                shell_error(classify_process_failure(&stderr, result.status.code()), stderr)
+            },
+            Err(e) => shell_error(ShellErrorKind::Io, format!("Failed to force-kill process: {}", e))
+        }
     } else {
-        Command::new("sh")
-            .arg("-c")
-            .arg(&command)
-            .output()
-    };
-    
-    match output {
-        Ok(result) => {
-            let success = result.status.success();
-            let stderr = String::from_utf8_lossy(&result.stderr);
-            
-            json!({
-                "success": success,
-                "result": {
-                    "pid": pid,
-                    "command": command,
-                    "killed": success,
-                    "method": "rust_process_kill"
-                },
-                "error": if success { serde_json::Value::Null } else { json!(stderr) }
-            })
-        },
-        Err(e) => {
-            json!({
-                "success": false,
-                "result": serde_json::Value::Null,
-                "error": format!("Failed to kill process: {}", e)
-            })
+        if !process_alive(pid) {
+            return shell_error(ShellErrorKind::NotFound, format!("No such process: {}", pid));
+        }
+
+        // An explicit signal is sent exactly once - the caller made the
+        // choice, we don't second-guess it with an escalation.
+        if let Some(requested) = signal {
+            let Some(sig) = parse_signal(requested) else {
+                return shell_error(ShellErrorKind::InvalidArgument, format!("Unknown signal: {}", requested));
+            };
+
+            return match send_signal(pid, sig) {
+                Ok(()) => json!({
+                    "success": true,
+                    "result": {
+                        "pid": pid,
+                        "signal": requested,
+                        "escalated": false,
+                        "exit_reason": if process_alive(pid) { "signal_sent" } else { "terminated" }
+                    },
+                    "error": serde_json::Value::Null
+                }),
+                Err(e) => shell_error(ShellErrorKind::Io, format!("Failed to signal process: {}", e)),
+            };
+        }
+
+        if let Err(e) = send_signal(pid, libc::SIGTERM) {
+            return shell_error(ShellErrorKind::Io, format!("Failed to signal process: {}", e));
+        }
+
+        std::thread::sleep(std::time::Duration::from_secs(grace_secs));
+
+        if !process_alive(pid) {
+            return json!({
+                "success": true,
+                "result": {"pid": pid, "signal": "SIGTERM", "escalated": false, "exit_reason": "terminated"},
+                "error": serde_json::Value::Null
+            });
+        }
+
+        match send_signal(pid, libc::SIGKILL) {
+            Ok(()) => json!({
+                "success": true,
+                "result": {"pid": pid, "signal": "SIGKILL", "escalated": true, "exit_reason": "killed"},
+                "error": serde_json::Value::Null
+            }),
+            Err(e) => shell_error(ShellErrorKind::Io, format!("Failed to force-kill process: {}", e))
         }
     }
 }
@@ -365,83 +887,129 @@ fn rust_which_command_impl(program: &str) -> serde_json::Value {
                     "error": serde_json::Value::Null
                 })
             } else {
-                json!({
-                    "success": false,
-                    "result": {
-                        "program": program,
-                        "path": serde_json::Value::Null,
-                        "exists": false
-                    },
-                    "error": format!("Program '{}' not found in PATH", program)
-                })
+                shell_error(ShellErrorKind::NotFound, format!("Program '{}' not found in PATH", program))
             }
         },
         Err(e) => {
-            json!({
-                "success": false,
-                "result": serde_json::Value::Null,
-                "error": format!("Failed to locate program: {}", e)
-            })
+            shell_error(ShellErrorKind::Io, format!("Failed to locate program: {}", e))
         }
     }
 }
 
-fn rust_list_processes_impl() -> serde_json::Value {
-    // Use platform-specific process listing
-    let command = if cfg!(target_os = "windows") {
-        "tasklist /FO CSV"
-    } else {
-        "ps aux"
-    };
-    
-    let output = if cfg!(target_os = "windows") {
-        Command::new("cmd")
-            .args(["/C", command])
-            .output()
-    } else {
-        Command::new("sh")
-            .arg("-c")
-            .arg(command)
-            .output()
-    };
-    
-    match output {
-        Ok(result) => {
-            let success = result.status.success();
-            let stdout = String::from_utf8_lossy(&result.stdout);
-            
-            if success {
-                // Parse process list (basic implementation)
-                let lines: Vec<&str> = stdout.lines().collect();
-                let process_count = lines.len().saturating_sub(1); // Subtract header
-                
-                json!({
-                    "success": true,
-                    "result": {
-                        "processes": stdout,
-                        "total_found": process_count,
-                        "method": "rust_process_list",
-                        "raw_output": true
-                    },
-                    "error": serde_json::Value::Null
-                })
-            } else {
-                let stderr = String::from_utf8_lossy(&result.stderr);
-                json!({
-                    "success": false,
-                    "result": serde_json::Value::Null,
-                    "error": stderr
-                })
-            }
-        },
-        Err(e) => {
+/// Snapshot of a single process, gathered once per `rust_list_processes_impl`
+/// call so it can be filtered/sorted before being shaped into either the
+/// current or the `raw_output` legacy JSON schema.
+struct ProcessSnapshot {
+    pid: u32,
+    ppid: Option<u32>,
+    user: Option<String>,
+    name: String,
+    command: String,
+    cpu_percent: f32,
+    mem_percent: f64,
+    rss_kb: u64,
+    memory_mb: f64,
+    status: String,
+    start_time: u64,
+    running_time_hours: f64,
+    disk_read_mb: f64,
+    disk_written_mb: f64,
+}
+
+/// Structured, cross-platform process listing via `sysinfo` (no more parsing
+/// `ps aux`/`tasklist` text by hand).
+fn rust_list_processes_impl(filter: Option<&str>, sort_by: Option<&str>, raw_output: bool) -> serde_json::Value {
+    let mut sys = System::new_all();
+    sys.refresh_processes();
+    // sysinfo derives cpu_usage() from the delta between two samples - a
+    // single refresh always reports ~0% for every process.
+    std::thread::sleep(std::time::Duration::from_millis(200));
+    sys.refresh_processes();
+    sys.refresh_users_list();
+
+    let total_memory = sys.total_memory().max(1) as f64;
+
+    let mut snapshots: Vec<ProcessSnapshot> = sys.processes().iter().map(|(process_pid, process)| {
+        let disk_usage = process.disk_usage();
+        let command = if process.cmd().is_empty() {
+            process.name().to_string()
+        } else {
+            process.cmd().join(" ")
+        };
+        let user = process.user_id().and_then(|uid| {
+            sys.users().iter().find(|u| u.id() == uid).map(|u| u.name().to_string())
+        });
+
+        ProcessSnapshot {
+            pid: process_pid.as_u32(),
+            ppid: process.parent().map(|p| p.as_u32()),
+            user,
+            name: process.name().to_string(),
+            command,
+            cpu_percent: process.cpu_usage(),
+            mem_percent: (process.memory() as f64 / total_memory) * 100.0,
+            rss_kb: process.memory() / 1024,
+            memory_mb: (process.memory() as f64 / (1024.0 * 1024.0) * 10.0).round() / 10.0,
+            status: format!("{:?}", process.status()),
+            start_time: process.start_time(),
+            running_time_hours: (process.run_time() as f64 / 3600.0 * 10.0).round() / 10.0,
+            disk_read_mb: (disk_usage.total_read_bytes as f64 / (1024.0 * 1024.0) * 10.0).round() / 10.0,
+            disk_written_mb: (disk_usage.total_written_bytes as f64 / (1024.0 * 1024.0) * 10.0).round() / 10.0,
+        }
+    }).collect();
+
+    if let Some(needle) = filter {
+        let needle = needle.to_lowercase();
+        snapshots.retain(|p| p.command.to_lowercase().contains(&needle) || p.name.to_lowercase().contains(&needle));
+    }
+
+    match sort_by.unwrap_or("mem") {
+        "cpu" => snapshots.sort_by(|a, b| b.cpu_percent.partial_cmp(&a.cpu_percent).unwrap_or(std::cmp::Ordering::Equal)),
+        "pid" => snapshots.sort_by_key(|p| p.pid),
+        _ => snapshots.sort_by(|a, b| b.memory_mb.partial_cmp(&a.memory_mb).unwrap_or(std::cmp::Ordering::Equal)),
+    }
+
+    let processes: Vec<serde_json::Value> = snapshots.iter().map(|p| {
+        if raw_output {
+            // Legacy shape, kept for callers that shipped against the
+            // original (pre-filter/sort) schema.
             json!({
-                "success": false,
-                "result": serde_json::Value::Null,
-                "error": format!("Failed to list processes: {}", e)
+                "pid": p.pid,
+                "name": p.name,
+                "parent_pid": p.ppid,
+                "cpu_percent": p.cpu_percent,
+                "memory_mb": p.memory_mb,
+                "status": p.status,
+                "start_time": p.start_time,
+                "running_time_hours": p.running_time_hours,
+                "disk_read_mb": p.disk_read_mb,
+                "disk_written_mb": p.disk_written_mb
+            })
+        } else {
+            json!({
+                "pid": p.pid,
+                "ppid": p.ppid,
+                "user": p.user,
+                "command": p.command,
+                "cpu_percent": p.cpu_percent,
+                "mem_percent": (p.mem_percent * 10.0).round() / 10.0,
+                "rss_kb": p.rss_kb,
+                "status": p.status,
+                "start_time": p.start_time,
+                "running_time_hours": p.running_time_hours
             })
         }
-    }
+    }).collect();
+
+    json!({
+        "success": true,
+        "result": {
+            "processes": processes,
+            "total_found": processes.len(),
+            "method": "rust_process_list"
+        },
+        "error": serde_json::Value::Null
+    })
 }
 
 fn rust_get_env_var_impl(key: &str) -> serde_json::Value {
@@ -664,3 +1232,148 @@ fn rust_create_virtual_env_impl(name: &str, path: Option<&str>) -> serde_json::V
         }
     }
 }
+
+/// Run `{binary} --version` and return the trimmed first line, or `None` if
+/// the binary isn't on PATH.
+fn tool_version(binary: &str, args: &[&str]) -> Option<String> {
+    Command::new(binary)
+        .args(args)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| {
+            let combined = if o.stdout.is_empty() { &o.stderr } else { &o.stdout };
+            String::from_utf8_lossy(combined).lines().next().unwrap_or("").trim().to_string()
+        })
+}
+
+/// Extracts the `[dependencies]` table of a `Cargo.toml` as a flat
+/// name -> version-requirement map. Handles both `name = "1.0"` and
+/// `name = { version = "1.0", ... }` forms; anything else is reported as-is.
+fn parse_cargo_toml_dependencies(contents: &str) -> serde_json::Value {
+    let mut deps = serde_json::Map::new();
+    let mut in_deps = false;
+    for line in contents.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            in_deps = trimmed == "[dependencies]";
+            continue;
+        }
+        if !in_deps || trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if let Some((name, rest)) = trimmed.split_once('=') {
+            let rest = rest.trim();
+            let version = if let Some(idx) = rest.find("version") {
+                rest[idx..].split('"').nth(1).unwrap_or(rest).to_string()
+            } else {
+                rest.trim_matches('"').to_string()
+            };
+            deps.insert(name.trim().to_string(), json!(version));
+        }
+    }
+    serde_json::Value::Object(deps)
+}
+
+/// Extracts `name`/`version` pairs out of a `Cargo.lock`'s `[[package]]` blocks.
+fn parse_cargo_lock_dependencies(contents: &str) -> serde_json::Value {
+    let mut deps = Vec::new();
+    let mut name: Option<String> = None;
+    let mut version: Option<String> = None;
+    for line in contents.lines() {
+        let trimmed = line.trim();
+        if trimmed == "[[package]]" {
+            if let (Some(n), Some(v)) = (name.take(), version.take()) {
+                deps.push(json!({"name": n, "version": v}));
+            }
+            continue;
+        }
+        if let Some(rest) = trimmed.strip_prefix("name = ") {
+            name = Some(rest.trim_matches('"').to_string());
+        } else if let Some(rest) = trimmed.strip_prefix("version = ") {
+            version = Some(rest.trim_matches('"').to_string());
+        }
+    }
+    if let (Some(n), Some(v)) = (name, version) {
+        deps.push(json!({"name": n, "version": v}));
+    }
+    json!(deps)
+}
+
+/// Report installed toolchain versions and, if the target directory looks
+/// like a project, its manifest-declared version.
+fn rust_environment_doctor_impl(path: Option<&str>) -> serde_json::Value {
+    let probed_tools = ["rustc", "cargo", "python3", "node", "git", "pip", "python"];
+
+    let toolchain = json!({
+        "rustc": tool_version("rustc", &["--version"]),
+        "cargo": tool_version("cargo", &["--version"]),
+        "python3": tool_version("python3", &["--version"]),
+        "node": tool_version("node", &["--version"]),
+        "git": tool_version("git", &["--version"]),
+        "pip": tool_version("pip", &["--version"]),
+        "python": tool_version("python", &["--version"]),
+    });
+
+    let mut path_available = serde_json::Map::new();
+    for tool in probed_tools {
+        let exists = rust_which_command_impl(tool)
+            .get("result")
+            .and_then(|r| r.get("exists"))
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        path_available.insert(tool.to_string(), json!(exists));
+    }
+
+    let project_dir = path.unwrap_or(".");
+    let mut project = json!({});
+
+    let cargo_toml = Path::new(project_dir).join("Cargo.toml");
+    if let Ok(contents) = std::fs::read_to_string(&cargo_toml) {
+        if let Some(version) = contents
+            .lines()
+            .find(|l| l.trim_start().starts_with("version"))
+            .and_then(|l| l.split('=').nth(1))
+        {
+            project["cargo_toml"] = json!(version.trim().trim_matches('"'));
+        }
+        project["cargo_toml_dependencies"] = parse_cargo_toml_dependencies(&contents);
+    }
+
+    let cargo_lock = Path::new(project_dir).join("Cargo.lock");
+    if let Ok(contents) = std::fs::read_to_string(&cargo_lock) {
+        project["cargo_lock_dependencies"] = parse_cargo_lock_dependencies(&contents);
+    }
+
+    let package_json = Path::new(project_dir).join("package.json");
+    if let Ok(contents) = std::fs::read_to_string(&package_json) {
+        if let Ok(parsed) = serde_json::from_str::<serde_json::Value>(&contents) {
+            project["package_json"] = parsed.get("version").cloned().unwrap_or(serde_json::Value::Null);
+            project["package_json_dependencies"] = parsed.get("dependencies").cloned().unwrap_or(json!({}));
+            project["package_json_dev_dependencies"] = parsed.get("devDependencies").cloned().unwrap_or(json!({}));
+        }
+    }
+
+    let pyproject_toml = Path::new(project_dir).join("pyproject.toml");
+    if let Ok(contents) = std::fs::read_to_string(&pyproject_toml) {
+        if let Some(version) = contents
+            .lines()
+            .find(|l| l.trim_start().starts_with("version"))
+            .and_then(|l| l.split('=').nth(1))
+        {
+            project["pyproject_toml"] = json!(version.trim().trim_matches('"'));
+        }
+    }
+
+    json!({
+        "success": true,
+        "result": {
+            "toolchain": toolchain,
+            "package_manager": detect_package_manager(),
+            "path_available": path_available,
+            "project": project,
+            "method": "rust_environment_doctor"
+        },
+        "error": serde_json::Value::Null
+    })
+}
@@ -5,9 +5,229 @@ use pyo3::prelude::*;
 use serde_json::json;
 use sysinfo::System;
 
+/// Queries battery state via the cross-platform `starship_battery` crate
+/// (sysfs on Linux, IOKit on macOS, Win32 on Windows), replacing the old
+/// per-OS shell-outs to `acpi`/`pmset`/`ioreg`/`wmic`.
+fn rust_battery_query_impl() -> serde_json::Value {
+    use starship_battery::units::energy::watt_hour;
+    use starship_battery::units::power::watt;
+    use starship_battery::units::ratio::percent;
+    use starship_battery::units::thermodynamic_temperature::degree_celsius;
+    use starship_battery::units::time::second;
+    use starship_battery::{Manager, State};
+
+    let no_battery = |message: String| {
+        json!({
+            "success": true,
+            "result": {
+                "batteries": [],
+                "count": 0,
+                "has_battery": false,
+                "message": message
+            },
+            "error": null
+        })
+    };
+
+    let manager = match Manager::new() {
+        Ok(manager) => manager,
+        Err(e) => return no_battery(format!("Battery subsystem unavailable: {}", e)),
+    };
+    let found = match manager.batteries() {
+        Ok(found) => found,
+        Err(e) => return no_battery(format!("Battery subsystem unavailable: {}", e)),
+    };
+
+    let mut batteries = Vec::new();
+    for (index, battery) in found.enumerate() {
+        let Ok(battery) = battery else { continue };
+
+        let status = match battery.state() {
+            State::Charging => "Charging",
+            State::Discharging => "Discharging",
+            State::Full => "Full",
+            State::Empty => "Empty",
+            _ => "Unknown",
+        };
+
+        let energy_wh = battery.energy().get::<watt_hour>() as f64;
+        let energy_full_wh = battery.energy_full().get::<watt_hour>() as f64;
+        let energy_full_design_wh = battery.energy_full_design().get::<watt_hour>() as f64;
+        let health_percent = (energy_full_design_wh > 0.0)
+            .then(|| ((energy_full_wh / energy_full_design_wh) * 100.0).round());
+
+        batteries.push(json!({
+            "name": battery.model().map(str::to_string).unwrap_or_else(|| format!("BAT{}", index)),
+            "level": (battery.state_of_charge().get::<percent>() as f64).round(),
+            "status": status,
+            "health_percent": health_percent,
+            "cycle_count": battery.cycle_count(),
+            "energy_wh": (energy_wh * 10.0).round() / 10.0,
+            "energy_full_wh": (energy_full_wh * 10.0).round() / 10.0,
+            "energy_rate_w": (battery.energy_rate().get::<watt>() as f64 * 10.0).round() / 10.0,
+            "temperature_c": battery.temperature().map(|t| t.get::<degree_celsius>() as f64),
+            "time_to_full_sec": battery.time_to_full().map(|t| t.get::<second>() as f64),
+            "time_to_empty_sec": battery.time_to_empty().map(|t| t.get::<second>() as f64),
+            "method": "starship_battery"
+        }));
+    }
+
+    if batteries.is_empty() {
+        return no_battery("No battery detected - likely desktop system".to_string());
+    }
+
+    let primary_battery = &batteries[0];
+    json!({
+        "success": true,
+        "result": {
+            "batteries": batteries,
+            "count": batteries.len(),
+            "has_battery": true,
+            "primary_level": primary_battery["level"],
+            "primary_status": primary_battery["status"],
+            "method": "starship_battery"
+        },
+        "error": null
+    })
+}
+
+/// Queries NVIDIA GPU stats via NVML (enabled with the `nvml` cargo feature,
+/// which links against the system's NVML/`libnvidia-ml` library). Falls
+/// back to shelling out to `nvidia-smi` when the feature is off, so this
+/// still works on builds that don't want the NVML dependency.
+#[cfg(feature = "nvml")]
+fn rust_gpu_query_impl() -> serde_json::Value {
+    use nvml_wrapper::enum_wrappers::device::TemperatureSensor;
+    use nvml_wrapper::Nvml;
+
+    let nvml = match Nvml::init() {
+        Ok(nvml) => nvml,
+        Err(_) => {
+            return json!({
+                "success": true,
+                "result": {
+                    "gpus": [],
+                    "count": 0,
+                    "has_gpu": false,
+                    "message": "NVML not available - no NVIDIA GPU or drivers detected"
+                },
+                "error": null
+            });
+        }
+    };
+
+    let device_count = nvml.device_count().unwrap_or(0);
+    let mut gpus = Vec::new();
+    for index in 0..device_count {
+        let Ok(device) = nvml.device_by_index(index) else { continue };
+
+        let memory = device.memory_info().ok();
+        let (memory_used_mb, memory_total_mb, used_percent) = match &memory {
+            Some(m) => (
+                Some(m.used as f64 / (1024.0 * 1024.0)),
+                Some(m.total as f64 / (1024.0 * 1024.0)),
+                (m.total > 0).then(|| (m.used as f64 / m.total as f64 * 100.0 * 10.0).round() / 10.0),
+            ),
+            None => (None, None, None),
+        };
+
+        gpus.push(json!({
+            "name": device.name().unwrap_or_default(),
+            "utilization_percent": device.utilization_rates().ok().map(|u| u.gpu as f64),
+            "memory_used_mb": memory_used_mb,
+            "memory_total_mb": memory_total_mb,
+            "used_percent": used_percent,
+            "temperature_c": device.temperature(TemperatureSensor::Gpu).ok().map(|t| t as f64),
+            "power_draw_w": device.power_usage().ok().map(|mw| mw as f64 / 1000.0),
+            "fan_speed_percent": device.fan_speed(0).ok().map(|pct| pct as f64)
+        }));
+    }
+
+    json!({
+        "success": true,
+        "result": {
+            "gpus": gpus,
+            "count": gpus.len(),
+            "has_gpu": !gpus.is_empty(),
+            "method": "nvml"
+        },
+        "error": null
+    })
+}
+
+#[cfg(not(feature = "nvml"))]
+fn rust_gpu_query_impl() -> serde_json::Value {
+    use std::process::Command;
+
+    let query = "name,utilization.gpu,memory.used,memory.total,temperature.gpu,power.draw,fan.speed";
+    let output = Command::new("nvidia-smi")
+        .args(["--query-gpu", query, "--format=csv,noheader,nounits"])
+        .output();
+
+    match output {
+        Ok(result) if result.status.success() => {
+            let text = String::from_utf8_lossy(&result.stdout);
+            let gpus: Vec<serde_json::Value> = text
+                .lines()
+                .filter_map(|line| {
+                    let fields: Vec<&str> = line.split(',').map(|f| f.trim()).collect();
+                    if fields.len() < 7 {
+                        return None;
+                    }
+                    let memory_used_mb = fields[2].parse::<f64>().ok();
+                    let memory_total_mb = fields[3].parse::<f64>().ok();
+                    let used_percent = match (memory_used_mb, memory_total_mb) {
+                        (Some(used), Some(total)) if total > 0.0 => Some((used / total * 100.0 * 10.0).round() / 10.0),
+                        _ => None,
+                    };
+                    Some(json!({
+                        "name": fields[0],
+                        "utilization_percent": fields[1].parse::<f64>().ok(),
+                        "memory_used_mb": memory_used_mb,
+                        "memory_total_mb": memory_total_mb,
+                        "used_percent": used_percent,
+                        "temperature_c": fields[4].parse::<f64>().ok(),
+                        "power_draw_w": fields[5].parse::<f64>().ok(),
+                        "fan_speed_percent": fields[6].parse::<f64>().ok()
+                    }))
+                })
+                .collect();
+
+            json!({
+                "success": true,
+                "result": {
+                    "gpus": gpus,
+                    "count": gpus.len(),
+                    "has_gpu": !gpus.is_empty(),
+                    "method": "nvidia_smi"
+                },
+                "error": null
+            })
+        },
+        _ => {
+            json!({
+                "success": true,
+                "result": {
+                    "gpus": [],
+                    "count": 0,
+                    "has_gpu": false,
+                    "message": "nvidia-smi not available - no NVIDIA GPU or drivers detected"
+                },
+                "error": null
+            })
+        }
+    }
+}
+
 /// Universal system query - consolidated system information tool
+///
+/// `network`'s `sample_ms` controls how the interface throughput is
+/// measured: omitted, it reports cumulative totals only (no extra delay);
+/// given, it sleeps that long between two samples and reports real
+/// per-second rates derived from that exact interval.
 #[pyfunction]
-pub fn rust_system_query(what: String) -> PyResult<String> {
+#[pyo3(signature = (what, sample_ms=None))]
+pub fn rust_system_query(what: String, sample_ms: Option<u64>) -> PyResult<String> {
     let mut sys = System::new_all();
     sys.refresh_all();
     
@@ -59,15 +279,20 @@ pub fn rust_system_query(what: String) -> PyResult<String> {
         "processes" => {
             let mut processes = Vec::new();
             for (pid, process) in sys.processes() {
+                let disk_usage = process.disk_usage();
                 processes.push(json!({
                     "pid": pid.as_u32(),
                     "name": process.name(),
+                    "parent_pid": process.parent().map(|p| p.as_u32()),
                     "cpu_percent": process.cpu_usage(),
                     "memory_mb": (process.memory() as f64 / (1024.0 * 1024.0) * 10.0).round() / 10.0,
-                    "status": format!("{:?}", process.status())
+                    "status": format!("{:?}", process.status()),
+                    "start_time": process.start_time(),
+                    "disk_read_mb": (disk_usage.total_read_bytes as f64 / (1024.0 * 1024.0) * 10.0).round() / 10.0,
+                    "disk_written_mb": (disk_usage.total_written_bytes as f64 / (1024.0 * 1024.0) * 10.0).round() / 10.0
                 }));
             }
-            
+
             // Sort by memory usage (top 50)
             processes.sort_by(|a, b| {
                 let mem_a = a["memory_mb"].as_f64().unwrap_or(0.0);
@@ -85,132 +310,117 @@ pub fn rust_system_query(what: String) -> PyResult<String> {
                 "error": null
             })
         },
-        "battery" => {
-            // Get battery information using system commands
-            use std::process::Command;
-            use std::fs;
-            
-            // Try Linux battery paths first
-            let mut batteries = Vec::new();
-            
-            // Check /sys/class/power_supply/ for battery info
-            if let Ok(entries) = fs::read_dir("/sys/class/power_supply/") {
-                for entry in entries.flatten() {
-                    let path = entry.path();
-                    let name = path.file_name().unwrap().to_string_lossy();
-                    
-                    if name.starts_with("BAT") {
-                        let capacity_path = path.join("capacity");
-                        let status_path = path.join("status");
-                        
-                        let level = fs::read_to_string(&capacity_path)
-                            .ok()
-                            .and_then(|s| s.trim().parse::<u8>().ok())
-                            .unwrap_or(0);
-                            
-                        let status = fs::read_to_string(&status_path)
-                            .ok()
-                            .map(|s| s.trim().to_string())
-                            .unwrap_or_else(|| "Unknown".to_string());
-                        
-                        batteries.push(json!({
-                            "name": name,
-                            "level": level,
-                            "status": status,
-                            "health": 100, // Default health
-                            "method": "linux_sysfs"
-                        }));
-                    }
-                }
-            }
-            
-            // If no batteries found via sysfs, try acpi command
-            if batteries.is_empty() {
-                if let Ok(output) = Command::new("acpi").arg("-b").output() {
-                    if output.status.success() {
-                        let acpi_output = String::from_utf8_lossy(&output.stdout);
-                        for line in acpi_output.lines() {
-                            if line.contains("Battery") {
-                                // Parse ACPI output: "Battery 0: Discharging, 85%, 02:15:30 remaining"
-                                let parts: Vec<&str> = line.split(',').collect();
-                                if parts.len() >= 2 {
-                                    let status = if line.contains("Charging") { "Charging" }
-                                               else if line.contains("Discharging") { "Discharging" }
-                                               else if line.contains("Full") { "Full" }
-                                               else { "Unknown" };
-                                    
-                                    let level = parts[1].trim()
-                                        .replace('%', "")
-                                        .parse::<u8>()
-                                        .unwrap_or(0);
-                                    
-                                    batteries.push(json!({
-                                        "name": "BAT0",
-                                        "level": level,
-                                        "status": status,
-                                        "health": 100,
-                                        "method": "linux_acpi"
-                                    }));
-                                }
-                            }
-                        }
-                    }
+        "battery" => rust_battery_query_impl(),
+        "network" => {
+            sys.refresh_networks();
+
+            // `received()`/`transmitted()` are deltas since the last refresh.
+            // Only take a second sample (and so only report rates) when the
+            // caller asked for one via `sample_ms` - otherwise this reports
+            // cumulative totals with no added latency.
+            let rate_elapsed_secs = sample_ms.map(|ms| {
+                std::thread::sleep(std::time::Duration::from_millis(ms));
+                sys.refresh_networks();
+                ms as f64 / 1000.0
+            });
+
+            let mut interfaces = Vec::new();
+            for (name, data) in sys.networks() {
+                let mut entry = json!({
+                    "name": name,
+                    "total_received_bytes": data.total_received(),
+                    "total_transmitted_bytes": data.total_transmitted(),
+                    "total_received_mb": (data.total_received() as f64 / (1024.0 * 1024.0) * 10.0).round() / 10.0,
+                    "total_transmitted_mb": (data.total_transmitted() as f64 / (1024.0 * 1024.0) * 10.0).round() / 10.0,
+                    "total_packets_received": data.total_packets_received(),
+                    "total_packets_transmitted": data.total_packets_transmitted(),
+                    "total_errors_on_received": data.total_errors_on_received(),
+                    "total_errors_on_transmitted": data.total_errors_on_transmitted(),
+                    "mac_address": data.mac_address().to_string()
+                });
+
+                if let Some(elapsed_secs) = rate_elapsed_secs {
+                    entry["rx_bytes_per_sec"] = json!((data.received() as f64 / elapsed_secs).round());
+                    entry["tx_bytes_per_sec"] = json!((data.transmitted() as f64 / elapsed_secs).round());
+                    entry["packets_received_per_sec"] = json!((data.packets_received() as f64 / elapsed_secs).round());
+                    entry["packets_transmitted_per_sec"] = json!((data.packets_transmitted() as f64 / elapsed_secs).round());
+                    entry["errors_on_received_per_sec"] = json!((data.errors_on_received() as f64 / elapsed_secs).round());
+                    entry["errors_on_transmitted_per_sec"] = json!((data.errors_on_transmitted() as f64 / elapsed_secs).round());
                 }
+
+                interfaces.push(entry);
             }
-            
-            if batteries.is_empty() {
-                json!({
-                    "success": true,
-                    "result": {
-                        "batteries": [],
-                        "count": 0,
-                        "has_battery": false,
-                        "message": "No battery detected - likely desktop system",
-                        "method": "rust_system_commands"
-                    },
-                    "error": null
-                })
-            } else {
-                let primary_battery = &batteries[0];
-                json!({
-                    "success": true,
-                    "result": {
-                        "batteries": batteries,
-                        "count": batteries.len(),
-                        "has_battery": true,
-                        "primary_level": primary_battery["level"],
-                        "primary_status": primary_battery["status"],
-                        "method": "rust_system_commands"
-                    },
-                    "error": null
-                })
-            }
-        },
-        "network" => {
+
             json!({
                 "success": true,
                 "result": {
-                    "interfaces": [],
-                    "note": "Network interface details not available in current sysinfo version"
+                    "interfaces": interfaces,
+                    "count": interfaces.len(),
+                    "sample_ms": sample_ms
                 },
                 "error": null
             })
         },
         "disk" => {
+            let mut disks = Vec::new();
+            for disk in sys.disks() {
+                let total = disk.total_space();
+                let available = disk.available_space();
+                let used = total.saturating_sub(available);
+
+                disks.push(json!({
+                    "name": disk.name().to_string_lossy(),
+                    "mount_point": disk.mount_point().to_string_lossy(),
+                    "file_system": String::from_utf8_lossy(disk.file_system()),
+                    "disk_type": format!("{:?}", disk.kind()),
+                    "total_gb": (total as f64 / (1024.0 * 1024.0 * 1024.0) * 100.0).round() / 100.0,
+                    "available_gb": (available as f64 / (1024.0 * 1024.0 * 1024.0) * 100.0).round() / 100.0,
+                    "used_percent": if total > 0 { ((used as f64 / total as f64) * 100.0).round() } else { 0.0 },
+                    "is_removable": disk.is_removable()
+                }));
+            }
+
+            json!({
+                "success": true,
+                "result": {
+                    "disks": disks,
+                    "count": disks.len()
+                },
+                "error": null
+            })
+        },
+        "temperature" => {
+            let mut sensors = Vec::new();
+            for component in sys.components() {
+                sensors.push(json!({
+                    "label": component.label(),
+                    "temperature_c": component.temperature(),
+                    "max_temperature_c": component.max(),
+                    "critical_temperature_c": component.critical()
+                }));
+            }
+
+            let hottest_c = sensors.iter()
+                .filter_map(|s| s["temperature_c"].as_f64())
+                .fold(None, |max: Option<f64>, v| Some(max.map_or(v, |m| m.max(v))));
+
             json!({
                 "success": true,
                 "result": {
-                    "disks": [],
-                    "note": "Disk information not available in current sysinfo version"
+                    "sensors": sensors,
+                    "count": sensors.len(),
+                    "has_sensors": !sensors.is_empty(),
+                    "hottest_c": hottest_c
                 },
                 "error": null
             })
         },
+        "gpu" => rust_gpu_query_impl(),
         _ => {
             json!({
                 "success": false,
                 "result": null,
-                "error": format!("Unknown query type: {}. Use: overview, battery, memory, cpu, network, processes, disk", what)
+                "error": format!("Unknown query type: {}. Use: overview, battery, memory, cpu, network, processes, disk, temperature, gpu", what)
             })
         }
     };
@@ -274,33 +484,327 @@ pub fn rust_system_control(action: String, level: Option<i32>, confirm: Option<b
     Ok(result.to_string())
 }
 
+fn bytes_to_mb(value: u64) -> f64 {
+    (value as f64 / (1024.0 * 1024.0) * 10.0).round() / 10.0
+}
+
+/// Parses `/proc/<pid>/cgroup` and returns the relative path for the given
+/// v1 controller (e.g. "memory", "cpu", "pids", "blkio"). The v2 unified
+/// hierarchy entry (`0::<path>`) is not matched here; see `cgroup_v2_path`.
+fn cgroup_controller_path(pid: u32, controller: &str) -> Option<String> {
+    let content = std::fs::read_to_string(format!("/proc/{}/cgroup", pid)).ok()?;
+    content.lines().find_map(|line| {
+        let mut parts = line.splitn(3, ':');
+        let _hierarchy_id = parts.next()?;
+        let controllers = parts.next()?;
+        let path = parts.next()?;
+        controllers.split(',').any(|c| c == controller).then(|| path.to_string())
+    })
+}
+
+fn cgroup_v2_path(pid: u32) -> Option<String> {
+    let content = std::fs::read_to_string(format!("/proc/{}/cgroup", pid)).ok()?;
+    content.lines().find(|l| l.starts_with("0::")).map(|l| l.splitn(3, ':').nth(2).unwrap_or("/").to_string())
+}
+
+/// Parses a flat `key value` file (`memory.stat`, `cpu.stat`) into a lookup map.
+fn read_cgroup_stat_file(path: &str) -> std::collections::HashMap<String, u64> {
+    std::fs::read_to_string(path)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let key = parts.next()?;
+            let value = parts.next()?.parse::<u64>().ok()?;
+            Some((key.to_string(), value))
+        })
+        .collect()
+}
+
+/// Parses cgroup v1's `blkio.throttle.io_service_bytes`, which lists one
+/// `<device> <op> <bytes>` line per device/operation pair.
+fn parse_blkio_v1(path: &str) -> Vec<serde_json::Value> {
+    let mut per_device: std::collections::HashMap<String, (u64, u64)> = std::collections::HashMap::new();
+    for line in std::fs::read_to_string(path).unwrap_or_default().lines() {
+        let mut parts = line.split_whitespace();
+        let (Some(device), Some(op), Some(value)) = (parts.next(), parts.next(), parts.next().and_then(|v| v.parse::<u64>().ok())) else {
+            continue;
+        };
+        let entry = per_device.entry(device.to_string()).or_insert((0, 0));
+        match op {
+            "Read" => entry.0 = value,
+            "Write" => entry.1 = value,
+            _ => {}
+        }
+    }
+    per_device.into_iter().map(|(device, (read_bytes, write_bytes))| {
+        json!({ "device": device, "read_bytes": read_bytes, "write_bytes": write_bytes })
+    }).collect()
+}
+
+/// Parses cgroup v2's `io.stat`, one `<device> rbytes=.. wbytes=.. ...` line per device.
+fn parse_io_stat_v2(path: &str) -> Vec<serde_json::Value> {
+    std::fs::read_to_string(path).unwrap_or_default().lines().map(|line| {
+        let mut parts = line.split_whitespace();
+        let device = parts.next().unwrap_or("").to_string();
+        let mut read_bytes = 0u64;
+        let mut write_bytes = 0u64;
+        for kv in parts {
+            if let Some((key, value)) = kv.split_once('=') {
+                match key {
+                    "rbytes" => read_bytes = value.parse().unwrap_or(0),
+                    "wbytes" => write_bytes = value.parse().unwrap_or(0),
+                    _ => {}
+                }
+            }
+        }
+        json!({ "device": device, "read_bytes": read_bytes, "write_bytes": write_bytes })
+    }).collect()
+}
+
+/// Resource usage/limits of the cgroup that `pid` belongs to - supports both
+/// cgroup v2 (single unified hierarchy) and v1 (per-controller hierarchies).
+fn rust_process_cgroup_impl(pid: u32) -> serde_json::Value {
+    if !std::path::Path::new(&format!("/proc/{}/cgroup", pid)).exists() {
+        return json!({
+            "success": false,
+            "result": null,
+            "error": format!("No such process: {}", pid)
+        });
+    }
+
+    if std::path::Path::new("/sys/fs/cgroup/cgroup.controllers").exists() {
+        let rel_path = cgroup_v2_path(pid).unwrap_or_else(|| "/".to_string());
+        let base = format!("/sys/fs/cgroup{}", rel_path);
+
+        let memory_current = std::fs::read_to_string(format!("{}/memory.current", base)).ok().and_then(|s| s.trim().parse::<u64>().ok());
+        let memory_max = std::fs::read_to_string(format!("{}/memory.max", base)).ok().and_then(|s| s.trim().parse::<u64>().ok());
+        let memory_stat = read_cgroup_stat_file(&format!("{}/memory.stat", base));
+
+        let cpu_max_raw = std::fs::read_to_string(format!("{}/cpu.max", base)).ok();
+        let (cpu_quota_us, cpu_period_us) = cpu_max_raw
+            .as_deref()
+            .map(|s| {
+                let mut parts = s.trim().split_whitespace();
+                let quota = parts.next().and_then(|q| q.parse::<u64>().ok());
+                let period = parts.next().and_then(|p| p.parse::<u64>().ok());
+                (quota, period)
+            })
+            .unwrap_or((None, None));
+        let cpu_stat = read_cgroup_stat_file(&format!("{}/cpu.stat", base));
+
+        let pids_current = std::fs::read_to_string(format!("{}/pids.current", base)).ok().and_then(|s| s.trim().parse::<u64>().ok());
+        let pids_max = std::fs::read_to_string(format!("{}/pids.max", base)).ok().and_then(|s| s.trim().parse::<u64>().ok());
+
+        json!({
+            "success": true,
+            "result": {
+                "pid": pid,
+                "version": "v2",
+                "cgroup_path": rel_path,
+                "memory": {
+                    "used_mb": memory_current.map(bytes_to_mb),
+                    "limit_mb": memory_max.map(bytes_to_mb),
+                    "cache_mb": memory_stat.get("file").map(|v| bytes_to_mb(*v)),
+                    "rss_mb": memory_stat.get("anon").map(|v| bytes_to_mb(*v))
+                },
+                "cpu": {
+                    "quota_us": cpu_quota_us,
+                    "period_us": cpu_period_us,
+                    "limit_cores": match (cpu_quota_us, cpu_period_us) {
+                        (Some(q), Some(p)) if p > 0 => Some((q as f64 / p as f64 * 100.0).round() / 100.0),
+                        _ => None
+                    },
+                    "nr_periods": cpu_stat.get("nr_periods"),
+                    "nr_throttled": cpu_stat.get("nr_throttled"),
+                    "throttled_usec": cpu_stat.get("throttled_usec")
+                },
+                "pids": { "current": pids_current, "limit": pids_max },
+                "blkio": parse_io_stat_v2(&format!("{}/io.stat", base))
+            },
+            "error": null
+        })
+    } else if std::path::Path::new("/sys/fs/cgroup/memory").exists() {
+        let memory_rel = cgroup_controller_path(pid, "memory").unwrap_or_else(|| "/".to_string());
+        let memory_base = format!("/sys/fs/cgroup/memory{}", memory_rel);
+        let memory_used = std::fs::read_to_string(format!("{}/memory.usage_in_bytes", memory_base)).ok().and_then(|s| s.trim().parse::<u64>().ok());
+        // An unconfigured v1 limit reads back as a huge sentinel (close to
+        // i64::MAX rounded to the page size) rather than being absent.
+        let memory_limit = std::fs::read_to_string(format!("{}/memory.limit_in_bytes", memory_base))
+            .ok()
+            .and_then(|s| s.trim().parse::<u64>().ok())
+            .filter(|v| *v < 1u64 << 62);
+        let memory_stat = read_cgroup_stat_file(&format!("{}/memory.stat", memory_base));
+
+        let cpu_rel = cgroup_controller_path(pid, "cpu").or_else(|| cgroup_controller_path(pid, "cpuacct")).unwrap_or_else(|| "/".to_string());
+        let cpu_base = format!("/sys/fs/cgroup/cpu{}", cpu_rel);
+        let cpu_quota_us = std::fs::read_to_string(format!("{}/cpu.cfs_quota_us", cpu_base))
+            .ok()
+            .and_then(|s| s.trim().parse::<i64>().ok())
+            .filter(|q| *q > 0)
+            .map(|q| q as u64);
+        let cpu_period_us = std::fs::read_to_string(format!("{}/cpu.cfs_period_us", cpu_base)).ok().and_then(|s| s.trim().parse::<u64>().ok());
+        let cpu_stat = read_cgroup_stat_file(&format!("{}/cpu.stat", cpu_base));
+
+        let pids_rel = cgroup_controller_path(pid, "pids").unwrap_or_else(|| "/".to_string());
+        let pids_base = format!("/sys/fs/cgroup/pids{}", pids_rel);
+        let pids_current = std::fs::read_to_string(format!("{}/pids.current", pids_base)).ok().and_then(|s| s.trim().parse::<u64>().ok());
+        let pids_max = std::fs::read_to_string(format!("{}/pids.max", pids_base)).ok().and_then(|s| s.trim().parse::<u64>().ok());
+
+        let blkio_rel = cgroup_controller_path(pid, "blkio").unwrap_or_else(|| "/".to_string());
+        let blkio_base = format!("/sys/fs/cgroup/blkio{}", blkio_rel);
+
+        json!({
+            "success": true,
+            "result": {
+                "pid": pid,
+                "version": "v1",
+                "cgroup_path": memory_rel,
+                "memory": {
+                    "used_mb": memory_used.map(bytes_to_mb),
+                    "limit_mb": memory_limit.map(bytes_to_mb),
+                    "cache_mb": memory_stat.get("cache").map(|v| bytes_to_mb(*v)),
+                    "rss_mb": memory_stat.get("rss").map(|v| bytes_to_mb(*v))
+                },
+                "cpu": {
+                    "quota_us": cpu_quota_us,
+                    "period_us": cpu_period_us,
+                    "limit_cores": match (cpu_quota_us, cpu_period_us) {
+                        (Some(q), Some(p)) if p > 0 => Some((q as f64 / p as f64 * 100.0).round() / 100.0),
+                        _ => None
+                    },
+                    "nr_periods": cpu_stat.get("nr_periods"),
+                    "nr_throttled": cpu_stat.get("nr_throttled"),
+                    "throttled_usec": cpu_stat.get("throttled_time").map(|v| v / 1000)
+                },
+                "pids": { "current": pids_current, "limit": pids_max },
+                "blkio": parse_blkio_v1(&format!("{}/blkio.throttle.io_service_bytes", blkio_base))
+            },
+            "error": null
+        })
+    } else {
+        json!({
+            "success": true,
+            "result": {
+                "pid": pid,
+                "version": "none",
+                "message": "No cgroup filesystem detected - likely running outside a container (or on a non-Linux host)"
+            },
+            "error": null
+        })
+    }
+}
+
+/// Total CPU time (user + system) a process has accumulated, read
+/// straight from `/proc/<pid>/stat` since sysinfo's `Process` only exposes
+/// an instantaneous `cpu_usage()` percentage, not a cumulative total.
+#[cfg(target_os = "linux")]
+fn process_cpu_time_secs(pid: u32) -> Option<f64> {
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    // `comm` (field 2) can itself contain spaces/parens, so split on the
+    // last ')' rather than whitespace to find the start of the numeric fields.
+    let after_comm = stat.rsplit_once(')')?.1;
+    let fields: Vec<&str> = after_comm.split_whitespace().collect();
+    // `state` is the first field after comm; utime/stime are fields 14/15
+    // overall, i.e. indices 11/12 in this slice.
+    let utime: u64 = fields.get(11)?.parse().ok()?;
+    let stime: u64 = fields.get(12)?.parse().ok()?;
+    let ticks_per_sec = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    let ticks_per_sec = if ticks_per_sec > 0 { ticks_per_sec as f64 } else { 100.0 };
+    Some((utime + stime) as f64 / ticks_per_sec)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn process_cpu_time_secs(_pid: u32) -> Option<f64> {
+    None
+}
+
+/// Builds one process entry for `list`/`find`. `prev_io` is the process's
+/// cumulative (read_bytes, written_bytes) from the sample taken
+/// `elapsed_secs` ago, used to derive instantaneous I/O rates; `None` when
+/// the process didn't exist in the earlier sample (e.g. just spawned).
+fn build_process_entry(pid: u32, process: &sysinfo::Process, prev_io: Option<(u64, u64)>, elapsed_secs: f64) -> serde_json::Value {
+    let disk_usage = process.disk_usage();
+    let (read_bytes_per_sec, write_bytes_per_sec) = match prev_io {
+        Some((prev_read, prev_write)) if elapsed_secs > 0.0 => (
+            Some((disk_usage.total_read_bytes.saturating_sub(prev_read) as f64 / elapsed_secs).round()),
+            Some((disk_usage.total_written_bytes.saturating_sub(prev_write) as f64 / elapsed_secs).round()),
+        ),
+        _ => (None, None),
+    };
+
+    json!({
+        "pid": pid,
+        "name": process.name(),
+        "parent_pid": process.parent().map(|p| p.as_u32()),
+        "exe": process.exe().map(|p| p.display().to_string()),
+        "command": if process.cmd().is_empty() { process.name().to_string() } else { process.cmd().join(" ") },
+        "cpu_percent": process.cpu_usage(),
+        "cpu_time_secs": process_cpu_time_secs(pid),
+        "memory_mb": (process.memory() as f64 / (1024.0 * 1024.0) * 10.0).round() / 10.0,
+        "status": format!("{:?}", process.status()),
+        "start_time": process.start_time(),
+        "running_time_hours": (process.run_time() as f64 / 3600.0 * 10.0).round() / 10.0,
+        "disk_read_mb": (disk_usage.total_read_bytes as f64 / (1024.0 * 1024.0) * 10.0).round() / 10.0,
+        "disk_written_mb": (disk_usage.total_written_bytes as f64 / (1024.0 * 1024.0) * 10.0).round() / 10.0,
+        "read_bytes_per_sec": read_bytes_per_sec,
+        "write_bytes_per_sec": write_bytes_per_sec
+    })
+}
+
 /// Universal process manager - consolidated process management tool
+///
+/// `kill` accepts an optional `signal` ("term", "kill", "int", "hup" -
+/// defaults to "term") so callers can choose a graceful or forceful stop.
+/// `cgroup` reports the resource limits/usage of the cgroup `pid` belongs
+/// to, resolved per-process via `/proc/<pid>/cgroup` (not the host root).
+/// `list`/`find` take two samples `sample_ms` apart (at least 50ms) when
+/// `sample_ms` is given, so `cpu_percent` and the `*_bytes_per_sec` I/O rates
+/// reflect real deltas; without it a single sample is taken and the
+/// `*_bytes_per_sec` fields are null.
 #[pyfunction]
-pub fn rust_process_manager(action: String, pid: Option<u32>, name: Option<String>) -> PyResult<String> {
+#[pyo3(signature = (action, pid=None, name=None, signal=None, sample_ms=None, confirm=None))]
+pub fn rust_process_manager(action: String, pid: Option<u32>, name: Option<String>, signal: Option<String>, sample_ms: Option<u64>, confirm: Option<bool>) -> PyResult<String> {
+    use std::collections::HashMap;
+    use std::time::Duration;
+
     let mut sys = System::new_all();
     sys.refresh_processes();
-    
+
+    // Sampling (a second refresh after a short sleep, to derive cpu/IO rate
+    // deltas) only makes sense for `list`/`find`, and only when the caller
+    // actually asked for it via `sample_ms` - otherwise this reports
+    // cumulative totals with no added latency, which matters most for
+    // `kill` (should act immediately) and `cgroup`/`apps` (don't use rates).
+    let needs_sampling = matches!(action.as_str(), "list" | "find") && sample_ms.is_some();
+
+    let (before_io, elapsed_secs): (HashMap<u32, (u64, u64)>, f64) = if needs_sampling {
+        let before_io: HashMap<u32, (u64, u64)> = sys.processes().iter().map(|(process_pid, process)| {
+            let disk_usage = process.disk_usage();
+            (process_pid.as_u32(), (disk_usage.total_read_bytes, disk_usage.total_written_bytes))
+        }).collect();
+
+        let sample_ms = sample_ms.unwrap().max(50);
+        std::thread::sleep(Duration::from_millis(sample_ms));
+        sys.refresh_processes();
+        (before_io, sample_ms as f64 / 1000.0)
+    } else {
+        (HashMap::new(), 0.0)
+    };
+
     let result = match action.as_str() {
         "list" => {
-            let mut processes = Vec::new();
-            for (process_pid, process) in sys.processes() {
-                processes.push(json!({
-                    "pid": process_pid.as_u32(),
-                    "name": process.name(),
-                    "cpu_percent": process.cpu_usage(),
-                    "memory_mb": (process.memory() as f64 / (1024.0 * 1024.0) * 10.0).round() / 10.0,
-                    "status": format!("{:?}", process.status()),
-                    "running_time_hours": (process.run_time() as f64 / 3600.0 * 10.0).round() / 10.0
-                }));
-            }
-            
+            let mut processes: Vec<serde_json::Value> = sys.processes().iter().map(|(process_pid, process)| {
+                build_process_entry(process_pid.as_u32(), process, before_io.get(&process_pid.as_u32()).copied(), elapsed_secs)
+            }).collect();
+
             // Sort by memory usage
             processes.sort_by(|a, b| {
                 let mem_a = a["memory_mb"].as_f64().unwrap_or(0.0);
                 let mem_b = b["memory_mb"].as_f64().unwrap_or(0.0);
                 mem_b.partial_cmp(&mem_a).unwrap_or(std::cmp::Ordering::Equal)
             });
-            
+
             json!({
                 "success": true,
                 "result": {
@@ -319,18 +823,14 @@ pub fn rust_process_manager(action: String, pid: Option<u32>, name: Option<Strin
                     "error": "Missing required argument: name"
                 })
             } else {
-                let mut found_processes = Vec::new();
-                for (process_pid, process) in sys.processes() {
-                    if process.name().to_lowercase().contains(&search_name.to_lowercase()) {
-                        found_processes.push(json!({
-                            "pid": process_pid.as_u32(),
-                            "name": process.name(),
-                            "cpu_percent": process.cpu_usage(),
-                            "memory_mb": (process.memory() as f64 / (1024.0 * 1024.0) * 10.0).round() / 10.0
-                        }));
-                    }
-                }
-                
+                let needle = search_name.to_lowercase();
+                let found_processes: Vec<serde_json::Value> = sys.processes().iter()
+                    .filter(|(_, process)| process.name().to_lowercase().contains(&needle))
+                    .map(|(process_pid, process)| {
+                        build_process_entry(process_pid.as_u32(), process, before_io.get(&process_pid.as_u32()).copied(), elapsed_secs)
+                    })
+                    .collect();
+
                 json!({
                     "success": true,
                     "result": {
@@ -349,13 +849,38 @@ pub fn rust_process_manager(action: String, pid: Option<u32>, name: Option<Strin
                     "result": null,
                     "error": "Missing required argument: pid"
                 })
-            } else {
-                // Process killing would require platform-specific implementation
+            } else if !confirm.unwrap_or(false) {
                 json!({
                     "success": false,
                     "result": null,
-                    "error": "Process killing not yet implemented in Rust backend"
+                    "error": "Dangerous operation 'kill' requires confirm=true"
                 })
+            } else {
+                let requested_signal = match signal.as_deref().unwrap_or("term").to_lowercase().as_str() {
+                    "kill" | "sigkill" | "9" => sysinfo::Signal::Kill,
+                    "int" | "sigint" | "2" => sysinfo::Signal::Interrupt,
+                    "hup" | "sighup" | "1" => sysinfo::Signal::Hangup,
+                    _ => sysinfo::Signal::Term,
+                };
+
+                match sys.process(sysinfo::Pid::from_u32(target_pid)) {
+                    Some(process) => {
+                        let sent = process.kill_with(requested_signal).unwrap_or(false);
+                        json!({
+                            "success": sent,
+                            "result": {
+                                "pid": target_pid,
+                                "signal": format!("{:?}", requested_signal)
+                            },
+                            "error": if sent { serde_json::Value::Null } else { json!("Failed to deliver signal") }
+                        })
+                    },
+                    None => json!({
+                        "success": false,
+                        "result": null,
+                        "error": format!("No such process: {}", target_pid)
+                    })
+                }
             }
         },
         "apps" => {
@@ -382,11 +907,23 @@ pub fn rust_process_manager(action: String, pid: Option<u32>, name: Option<Strin
                 "error": null
             })
         },
+        "cgroup" => {
+            let target_pid = pid.unwrap_or(0);
+            if target_pid == 0 {
+                json!({
+                    "success": false,
+                    "result": null,
+                    "error": "Missing required argument: pid"
+                })
+            } else {
+                rust_process_cgroup_impl(target_pid)
+            }
+        },
         _ => {
             json!({
                 "success": false,
                 "result": null,
-                "error": format!("Unknown action: {}. Use: list, apps, find, kill", action)
+                "error": format!("Unknown action: {}. Use: list, apps, find, kill, cgroup", action)
             })
         }
     };
@@ -3,38 +3,160 @@
 
 use pyo3::prelude::*;
 use serde_json::json;
+use sha2::{Digest, Sha256};
 use std::fs;
 use std::process::Command;
 use std::time::{Duration, Instant};
 
-/// Fast file reading in Rust
-#[pyfunction]
-pub fn rust_read_file(file_path: String) -> PyResult<String> {
-    match fs::read_to_string(&file_path) {
-        Ok(content) => {
-            let result = json!({
-                "success": true,
-                "result": {
-                    "path": file_path,
-                    "content": content,
-                    "size": content.len(),
-                    "type": "text"
-                },
-                "error": null
-            });
-            Ok(result.to_string())
-        }
-        Err(e) => {
-            let result = json!({
+/// Best-effort MIME type from a file extension, covering the binary/media
+/// types callers actually ask to read (images, audio, video, archives, PDFs).
+/// Unknown extensions fall back to a generic octet-stream.
+fn mime_type_for(path: &std::path::Path) -> &'static str {
+    match path.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase().as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "bmp" => "image/bmp",
+        "svg" => "image/svg+xml",
+        "ico" => "image/x-icon",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "ogg" => "audio/ogg",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        "mov" => "video/quicktime",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        "gz" => "application/gzip",
+        "tar" => "application/x-tar",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Extensions that are always treated as binary media, regardless of
+/// whether their bytes happen to decode as UTF-8.
+const BINARY_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "webp", "gif", "bmp", "ico", "pdf", "mp3", "wav", "ogg", "mp4", "webm", "mov", "zip", "gz", "tar"];
+
+/// Decide whether `path` should be read as binary: known media extensions
+/// always are; otherwise a prefix of the file is scanned for a NUL byte or
+/// invalid UTF-8, which catches binary formats without a recognized
+/// extension instead of waiting for `read_to_string` to fail outright.
+fn looks_binary(path: &std::path::Path) -> bool {
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
+    if BINARY_EXTENSIONS.contains(&ext.as_str()) {
+        return true;
+    }
+
+    let mut file = match fs::File::open(path) {
+        Ok(f) => f,
+        Err(_) => return false,
+    };
+    use std::io::Read;
+    let mut buf = [0u8; 8192];
+    let n = match file.read(&mut buf) {
+        Ok(n) => n,
+        Err(_) => return false,
+    };
+    let prefix = &buf[..n];
+    // A multi-byte character cut off at the end of the prefix is not invalid
+    // UTF-8 (`error_len()` is `None` for a truncated sequence).
+    prefix.contains(&0) || matches!(std::str::from_utf8(prefix), Err(e) if e.error_len().is_some())
+}
+
+/// Read a file's raw bytes as a base64 `data:` URL, for binary/media content
+/// that isn't valid UTF-8 text.
+fn read_as_data_url(path: &std::path::Path) -> std::io::Result<String> {
+    use base64::Engine;
+    let bytes = fs::read(path)?;
+    let encoded = base64::engine::general_purpose::STANDARD.encode(&bytes);
+    Ok(format!("data:{};base64,{}", mime_type_for(path), encoded))
+}
+
+/// Read `path` as either plain text (`type: "text"`) or, for binary/media
+/// content detected by `looks_binary`, a base64 `data:` URL (`type:
+/// "binary"`) carrying its MIME type. Shared by `rust_read_file` and
+/// `rust_file_manager`'s `"read"` action so both report the same shape.
+fn read_file_contents(path: &std::path::Path) -> serde_json::Value {
+    if looks_binary(path) {
+        return match read_as_data_url(path) {
+            Ok(data_url) => {
+                let size = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+                json!({
+                    "success": true,
+                    "result": {
+                        "path": path.to_string_lossy(),
+                        "data_url": data_url,
+                        "size": size,
+                        "type": "binary",
+                        "encoding": "base64",
+                        "mime_type": mime_type_for(path)
+                    },
+                    "error": null
+                })
+            },
+            Err(e) => json!({
                 "success": false,
                 "result": null,
                 "error": format!("Failed to read file: {}", e)
-            });
-            Ok(result.to_string())
-        }
+            })
+        };
+    }
+
+    match fs::read_to_string(path) {
+        Ok(content) => json!({
+            "success": true,
+            "result": {
+                "path": path.to_string_lossy(),
+                "content": content,
+                "size": content.len(),
+                "type": "text",
+                "encoding": "utf-8"
+            },
+            "error": null
+        }),
+        Err(e) if e.kind() == std::io::ErrorKind::InvalidData => {
+            // Extension/prefix scan missed it - fall back to the binary path.
+            match read_as_data_url(path) {
+                Ok(data_url) => {
+                    let size = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+                    json!({
+                        "success": true,
+                        "result": {
+                            "path": path.to_string_lossy(),
+                            "data_url": data_url,
+                            "size": size,
+                            "type": "binary",
+                            "encoding": "base64",
+                            "mime_type": mime_type_for(path)
+                        },
+                        "error": null
+                    })
+                },
+                Err(e) => json!({
+                    "success": false,
+                    "result": null,
+                    "error": format!("Failed to read file: {}", e)
+                })
+            }
+        },
+        Err(e) => json!({
+            "success": false,
+            "result": null,
+            "error": format!("Failed to read file: {}", e)
+        })
     }
 }
 
+/// Fast file reading in Rust
+///
+/// Falls back to a base64 `data:` URL (`type: "binary"`) for files that are
+/// detected as binary/media - images, audio, video, archives, PDFs, etc.
+#[pyfunction]
+pub fn rust_read_file(file_path: String) -> PyResult<String> {
+    Ok(read_file_contents(std::path::Path::new(&file_path)).to_string())
+}
+
 /// Fast file writing in Rust
 #[pyfunction]
 pub fn rust_write_file(file_path: String, content: String) -> PyResult<String> {
@@ -111,74 +233,205 @@ pub fn rust_list_files(dir_path: String) -> PyResult<String> {
     }
 }
 
-/// Fast network ping in Rust
+/// Attempt a single TCP connection to `host:443`, returning the round-trip
+/// time on success. Used as a pure-Rust, no-shell-out stand-in for ICMP
+/// ping/reachability checks (raw ICMP sockets require elevated privileges
+/// we can't assume here; a TCP connect is a reasonable proxy and port 443
+/// is open on essentially every reachable host).
+fn tcp_probe(host: &str, timeout: Duration) -> Result<Duration, String> {
+    use std::net::{TcpStream, ToSocketAddrs};
+
+    let addr = (host, 443)
+        .to_socket_addrs()
+        .map_err(|e| format!("Could not resolve host: {}", e))?
+        .next()
+        .ok_or_else(|| format!("No addresses found for host: {}", host))?;
+
+    let start = Instant::now();
+    TcpStream::connect_timeout(&addr, timeout).map_err(|e| e.to_string())?;
+    Ok(start.elapsed())
+}
+
+/// Fast network reachability check in Rust
+///
+/// Pings via `count` sequential TCP connect attempts to port 443 rather
+/// than shelling out to the system `ping` binary - no ICMP privileges
+/// needed and no dependency on a `ping` executable being on `PATH`.
 #[pyfunction]
 pub fn rust_ping_host(host: String, count: Option<u32>) -> PyResult<String> {
-    let ping_count = count.unwrap_or(4);
-    
-    // Use system ping command for now (could use pure Rust ping library later)
-    let output = Command::new("ping")
-        .arg("-c")
-        .arg(ping_count.to_string())
-        .arg(&host)
-        .output();
-    
-    match output {
-        Ok(result) => {
-            let stdout = String::from_utf8_lossy(&result.stdout);
-            let stderr = String::from_utf8_lossy(&result.stderr);
-            
-            let response = json!({
-                "success": result.status.success(),
-                "result": {
-                    "host": host,
-                    "count": ping_count,
-                    "output": stdout,
-                    "reachable": result.status.success()
-                },
-                "error": if result.status.success() { serde_json::Value::Null } else { serde_json::Value::String(stderr.to_string()) }
-            });
-            Ok(response.to_string())
+    let ping_count = count.unwrap_or(4).max(1);
+
+    let mut received = 0u32;
+    let mut latencies_ms: Vec<f64> = Vec::new();
+    let mut lines: Vec<String> = Vec::new();
+
+    for seq in 1..=ping_count {
+        match tcp_probe(&host, Duration::from_secs(2)) {
+            Ok(rtt) => {
+                let ms = rtt.as_secs_f64() * 1000.0;
+                latencies_ms.push(ms);
+                received += 1;
+                lines.push(format!("Reply from {} (seq={}): time={:.2}ms", host, seq, ms));
+            }
+            Err(e) => {
+                lines.push(format!("seq={} failed: {}", seq, e));
+            }
         }
-        Err(e) => {
-            let result = json!({
-                "success": false,
-                "result": null,
-                "error": format!("Failed to ping host: {}", e)
-            });
-            Ok(result.to_string())
+    }
+
+    let reachable = received > 0;
+    let packet_loss = ((ping_count - received) as f64 / ping_count as f64) * 100.0;
+    let stats = json!({
+        "packets_sent": ping_count,
+        "packets_received": received,
+        "packet_loss": packet_loss,
+        "min_time": latencies_ms.iter().cloned().fold(f64::INFINITY, f64::min).is_finite()
+            .then(|| latencies_ms.iter().cloned().fold(f64::INFINITY, f64::min)),
+        "max_time": latencies_ms.iter().cloned().fold(f64::NEG_INFINITY, f64::max).is_finite()
+            .then(|| latencies_ms.iter().cloned().fold(f64::NEG_INFINITY, f64::max)),
+        "avg_time": if latencies_ms.is_empty() { None } else { Some(latencies_ms.iter().sum::<f64>() / latencies_ms.len() as f64) }
+    });
+
+    let response = json!({
+        "success": reachable,
+        "result": {
+            "host": host,
+            "count": ping_count,
+            "statistics": stats,
+            "output": lines.join("\n"),
+            "reachable": reachable
+        },
+        "error": if reachable { serde_json::Value::Null } else { serde_json::Value::String(format!("Host {} unreachable", host)) }
+    });
+    Ok(response.to_string())
+}
+
+/// Drain a child's pipe to completion on whatever thread calls this -
+/// meant to be run on its own thread per pipe so stdout/stderr don't starve
+/// each other while we separately wait on the child's exit status.
+fn drain_pipe(mut pipe: impl std::io::Read) -> Vec<u8> {
+    use std::io::Read;
+    let mut buf = Vec::new();
+    let _ = pipe.read_to_end(&mut buf);
+    buf
+}
+
+/// Kill `child`'s whole process group, not just the immediate `sh` process,
+/// so shell-spawned grandchildren (e.g. a pipeline or a backgrounded
+/// subprocess) die with it too. Relies on `process_group(0)` having been set
+/// at spawn time so the child is its own group leader. On platforms without
+/// process groups, falls back to killing just the direct child.
+fn kill_process_group(child: &mut std::process::Child) {
+    #[cfg(unix)]
+    {
+        unsafe {
+            libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL);
         }
     }
+    #[cfg(not(unix))]
+    {
+        let _ = child.kill();
+    }
 }
 
 /// Fast command execution in Rust
+///
+/// `timeout_secs` (default 30) is enforced: the whole process group is
+/// killed and `timed_out: true` is reported if it's still running once the
+/// timeout elapses, instead of the caller blocking forever. stdout/stderr
+/// are drained on separate threads concurrently with waiting on the child,
+/// so a chatty command can't deadlock against a full pipe buffer. `cwd` and
+/// `env` scope the working directory and extra environment variables for
+/// the spawned shell.
 #[pyfunction]
-pub fn rust_run_command(command: String, timeout_secs: Option<u64>) -> PyResult<String> {
-    let _timeout = Duration::from_secs(timeout_secs.unwrap_or(30));
+#[pyo3(signature = (command, timeout_secs=None, cwd=None, env=None))]
+pub fn rust_run_command(
+    command: String,
+    timeout_secs: Option<u64>,
+    cwd: Option<String>,
+    env: Option<std::collections::HashMap<String, String>>,
+) -> PyResult<String> {
+    use std::process::Stdio;
+    use wait_timeout::ChildExt;
+
+    let timeout = Duration::from_secs(timeout_secs.unwrap_or(30));
     let start_time = Instant::now();
-    
-    let output = Command::new("sh")
-        .arg("-c")
-        .arg(&command)
-        .output();
-    
-    let elapsed = start_time.elapsed();
-    
-    match output {
-        Ok(result) => {
-            let stdout = String::from_utf8_lossy(&result.stdout);
-            let stderr = String::from_utf8_lossy(&result.stderr);
-            
+
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg(&command);
+    cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+    if let Some(cwd) = &cwd {
+        cmd.current_dir(cwd);
+    }
+    if let Some(env) = &env {
+        for (key, value) in env {
+            cmd.env(key, value);
+        }
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        // Make the child its own process group leader so kill_process_group
+        // can signal it and everything it spawns in one shot.
+        cmd.process_group(0);
+    }
+
+    let mut child = match cmd.spawn() {
+        Ok(c) => c,
+        Err(e) => {
+            let result = json!({
+                "success": false,
+                "result": null,
+                "error": format!("Failed to run command: {}", e)
+            });
+            return Ok(result.to_string());
+        }
+    };
+
+    let stdout_pipe = child.stdout.take();
+    let stderr_pipe = child.stderr.take();
+    let stdout_reader = std::thread::spawn(move || stdout_pipe.map(drain_pipe).unwrap_or_default());
+    let stderr_reader = std::thread::spawn(move || stderr_pipe.map(drain_pipe).unwrap_or_default());
+
+    match child.wait_timeout(timeout) {
+        Ok(Some(status)) => {
+            let stdout = String::from_utf8_lossy(&stdout_reader.join().unwrap_or_default()).to_string();
+            let stderr = String::from_utf8_lossy(&stderr_reader.join().unwrap_or_default()).to_string();
+            let elapsed = start_time.elapsed();
+
+            let response = json!({
+                "success": status.success(),
+                "result": {
+                    "command": command,
+                    "return_code": status.code().unwrap_or(-1),
+                    "stdout": stdout,
+                    "stderr": stderr,
+                    "elapsed_ms": elapsed.as_millis(),
+                    "timed_out": false
+                },
+                "error": if status.success() { serde_json::Value::Null } else { serde_json::Value::String(stderr) }
+            });
+            Ok(response.to_string())
+        }
+        Ok(None) => {
+            // Timed out - kill the whole process group so it doesn't keep running in the background.
+            kill_process_group(&mut child);
+            let _ = child.wait();
+            let stdout = String::from_utf8_lossy(&stdout_reader.join().unwrap_or_default()).to_string();
+            let stderr = String::from_utf8_lossy(&stderr_reader.join().unwrap_or_default()).to_string();
+            let elapsed = start_time.elapsed();
+
             let response = json!({
-                "success": result.status.success(),
+                "success": false,
                 "result": {
                     "command": command,
-                    "return_code": result.status.code().unwrap_or(-1),
+                    "return_code": serde_json::Value::Null,
                     "stdout": stdout,
                     "stderr": stderr,
-                    "elapsed_ms": elapsed.as_millis()
+                    "elapsed_ms": elapsed.as_millis(),
+                    "timed_out": true
                 },
-                "error": if result.status.success() { serde_json::Value::Null } else { serde_json::Value::String(stderr.to_string()) }
+                "error": format!("Command timed out after {} seconds", timeout.as_secs())
             });
             Ok(response.to_string())
         }
@@ -186,7 +439,7 @@ pub fn rust_run_command(command: String, timeout_secs: Option<u64>) -> PyResult<
             let result = json!({
                 "success": false,
                 "result": null,
-                "error": format!("Failed to run command: {}", e)
+                "error": format!("Failed to wait for command: {}", e)
             });
             Ok(result.to_string())
         }
@@ -194,33 +447,27 @@ pub fn rust_run_command(command: String, timeout_secs: Option<u64>) -> PyResult<
 }
 
 /// Check internet connectivity in Rust
+///
+/// Probes common DNS resolvers with a TCP connect to port 443 instead of
+/// shelling out to `ping`.
 #[pyfunction]
 pub fn rust_check_internet() -> PyResult<String> {
-    // Try to ping common DNS servers
     let test_hosts = vec!["8.8.8.8", "1.1.1.1", "google.com"];
     let mut connected = false;
     let mut results = Vec::new();
-    
+
     for host in test_hosts {
-        let output = Command::new("ping")
-            .arg("-c")
-            .arg("1")
-            .arg("-W")
-            .arg("3")
-            .arg(host)
-            .output();
-        
-        let success = output.map(|o| o.status.success()).unwrap_or(false);
+        let success = tcp_probe(host, Duration::from_secs(3)).is_ok();
         results.push(json!({
             "host": host,
             "reachable": success
         }));
-        
+
         if success {
             connected = true;
         }
     }
-    
+
     let response = json!({
         "success": true,
         "result": {
@@ -232,9 +479,353 @@ pub fn rust_check_internet() -> PyResult<String> {
     Ok(response.to_string())
 }
 
+/// Classify a `notify` event into the coarse created/modified/deleted/renamed
+/// vocabulary `rust_watch_path` reports.
+fn classify_watch_event(kind: &notify::EventKind) -> &'static str {
+    use notify::event::ModifyKind;
+    use notify::EventKind;
+
+    match kind {
+        EventKind::Create(_) => "created",
+        EventKind::Remove(_) => "deleted",
+        EventKind::Modify(ModifyKind::Name(_)) => "renamed",
+        EventKind::Modify(_) => "modified",
+        _ => "modified",
+    }
+}
+
+/// Block until filesystem changes occur under `path`, then return the
+/// coalesced set of events instead of making Python callers busy-poll
+/// `rust_list_files`/`info` timestamps to notice a change.
+///
+/// `recursive` defaults to true. The call waits up to `timeout_ms` (default
+/// 30000) for the first change; if none arrives it returns
+/// `{"changed": false}`. Once a change is seen, further events arriving
+/// within `debounce_ms` (default 300) of each other are coalesced into the
+/// same batch so a burst of saves is reported as one set of events.
+#[pyfunction]
+#[pyo3(signature = (path, recursive=None, debounce_ms=None, timeout_ms=None))]
+pub fn rust_watch_path(
+    path: String,
+    recursive: Option<bool>,
+    debounce_ms: Option<u64>,
+    timeout_ms: Option<u64>,
+) -> PyResult<String> {
+    use notify::{RecursiveMode, Watcher};
+    use std::collections::HashMap;
+    use std::sync::mpsc::channel;
+
+    let recursive_mode = if recursive.unwrap_or(true) {
+        RecursiveMode::Recursive
+    } else {
+        RecursiveMode::NonRecursive
+    };
+    let debounce = Duration::from_millis(debounce_ms.unwrap_or(300));
+    let timeout = Duration::from_millis(timeout_ms.unwrap_or(30_000));
+
+    let (tx, rx) = channel();
+    let mut watcher = match notify::recommended_watcher(move |res| {
+        let _ = tx.send(res);
+    }) {
+        Ok(w) => w,
+        Err(e) => {
+            let result = json!({
+                "success": false,
+                "result": null,
+                "error": format!("Failed to create file watcher: {}", e)
+            });
+            return Ok(result.to_string());
+        }
+    };
+
+    if let Err(e) = watcher.watch(std::path::Path::new(&path), recursive_mode) {
+        let result = json!({
+            "success": false,
+            "result": null,
+            "error": format!("Failed to watch path {}: {}", path, e)
+        });
+        return Ok(result.to_string());
+    }
+
+    // Wait for the first change within the overall timeout window.
+    let first_event: notify::Result<notify::Event> = match rx.recv_timeout(timeout) {
+        Ok(res) => res,
+        Err(_) => {
+            let response = json!({
+                "success": true,
+                "result": {"changed": false, "events": []},
+                "error": null
+            });
+            return Ok(response.to_string());
+        }
+    };
+
+    let mut batch: HashMap<String, &'static str> = HashMap::new();
+    if let Ok(event) = first_event {
+        let kind = classify_watch_event(&event.kind);
+        for p in &event.paths {
+            batch.insert(p.to_string_lossy().to_string(), kind);
+        }
+    }
+
+    // Coalesce any further events that land within the debounce window.
+    loop {
+        match rx.recv_timeout(debounce) {
+            Ok(Ok(event)) => {
+                let kind = classify_watch_event(&event.kind);
+                for p in &event.paths {
+                    batch.insert(p.to_string_lossy().to_string(), kind);
+                }
+            }
+            Ok(Err(_)) => continue,
+            Err(_) => break,
+        }
+    }
+
+    let events: Vec<serde_json::Value> = batch
+        .into_iter()
+        .map(|(changed_path, kind)| json!({"path": changed_path, "kind": kind}))
+        .collect();
+
+    let response = json!({
+        "success": true,
+        "result": {
+            "changed": true,
+            "count": events.len(),
+            "events": events
+        },
+        "error": null
+    });
+    Ok(response.to_string())
+}
+
+/// Rolling-hash window width (bytes) for content-defined chunking.
+const CDC_WINDOW: usize = 48;
+/// Multiplicative base for the Rabin-style rolling hash, an arbitrary odd
+/// 64-bit constant (the 64-bit FNV prime) so old bytes can be "rolled out"
+/// of the window with a single wrapping multiply/subtract.
+const CDC_BASE: u64 = 1_099_511_628_211;
+
+/// Split `data` into content-defined chunks: a rolling hash slides a
+/// `CDC_WINDOW`-byte window over the bytes, and a boundary is cut wherever
+/// the low bits of the hash are all zero under a mask sized for `avg_size`,
+/// clamped to `[min_size, max_size]`. Returns `(offset, length, sha256_hex)`
+/// per chunk.
+///
+/// Because the cut points are driven by local content rather than a fixed
+/// stride, inserting/deleting bytes in the middle of a file only reshuffles
+/// the chunks touching the edit - the rest stay byte-identical, which is
+/// what makes this dedup-friendly across repeated backups.
+fn chunk_content(data: &[u8], min_size: usize, avg_size: usize, max_size: usize) -> Vec<(usize, usize, String)> {
+    if data.is_empty() {
+        return Vec::new();
+    }
+
+    let min_size = min_size.max(1);
+    let avg_size = avg_size.max(min_size);
+    let max_size = max_size.max(avg_size);
+
+    let mask_bits = (avg_size as f64).log2().round().max(1.0) as u32;
+    let mask = (1u64 << mask_bits.min(63)) - 1;
+    let base_pow_window = CDC_BASE.wrapping_pow(CDC_WINDOW as u32);
+
+    let mut chunks = Vec::new();
+    let mut chunk_start = 0usize;
+    let mut hash: u64 = 0;
+
+    for i in 0..data.len() {
+        hash = hash.wrapping_mul(CDC_BASE).wrapping_add(data[i] as u64);
+        // Only roll out a byte once it's within the window *of the current
+        // chunk* - `hash` is reset to 0 at each boundary below, so indexing
+        // off the absolute position `i` here would subtract a byte that was
+        // never folded into this chunk's hash.
+        if i - chunk_start >= CDC_WINDOW {
+            hash = hash.wrapping_sub((data[i - CDC_WINDOW] as u64).wrapping_mul(base_pow_window));
+        }
+
+        let chunk_len = i - chunk_start + 1;
+        let window_full = i + 1 - chunk_start >= CDC_WINDOW;
+        let is_boundary = chunk_len >= max_size || (chunk_len >= min_size && window_full && hash & mask == 0);
+
+        if is_boundary {
+            chunks.push((chunk_start, chunk_len, sha256_hex(&data[chunk_start..=i])));
+            chunk_start = i + 1;
+            hash = 0;
+        }
+    }
+
+    if chunk_start < data.len() {
+        let slice = &data[chunk_start..];
+        chunks.push((chunk_start, slice.len(), sha256_hex(slice)));
+    }
+
+    chunks
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    format!("{:x}", hasher.finalize())
+}
+
+/// Copy `source` into `dest` by content-defined chunks, writing a chunk into
+/// the destination's chunk store only if its hash isn't already present
+/// there, so repeated backups of a large, slowly-changing file store
+/// duplicate chunks only once. The destination file itself is still
+/// (re)assembled and written in full each call - it's the chunk store,
+/// and `new_chunks`/`bytes_transferred` in the result, that reflect the
+/// savings.
+fn copy_file_dedup(
+    source: &std::path::Path,
+    dest: &std::path::Path,
+    min_size: usize,
+    avg_size: usize,
+    max_size: usize,
+) -> std::io::Result<serde_json::Value> {
+    let data = fs::read(source)?;
+    let chunks = chunk_content(&data, min_size, avg_size, max_size);
+
+    let store_dir = dest.with_file_name(format!(
+        "{}.chunkstore",
+        dest.file_name().unwrap_or_default().to_string_lossy()
+    ));
+    fs::create_dir_all(&store_dir)?;
+
+    let mut new_chunks = 0u64;
+    let mut reused_chunks = 0u64;
+    let mut bytes_transferred = 0u64;
+    let mut assembled = Vec::with_capacity(data.len());
+
+    for (offset, length, hash) in &chunks {
+        let chunk_bytes = &data[*offset..*offset + *length];
+        let chunk_path = store_dir.join(hash);
+
+        if chunk_path.exists() {
+            reused_chunks += 1;
+        } else {
+            fs::write(&chunk_path, chunk_bytes)?;
+            new_chunks += 1;
+            bytes_transferred += *length as u64;
+        }
+
+        assembled.extend_from_slice(chunk_bytes);
+    }
+
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(dest, &assembled)?;
+
+    Ok(json!({
+        "from": source.to_string_lossy(),
+        "to": dest.to_string_lossy(),
+        "action": "file_copied_dedup",
+        "total_chunks": chunks.len(),
+        "new_chunks": new_chunks,
+        "reused_chunks": reused_chunks,
+        "bytes_transferred": bytes_transferred,
+        "total_bytes": data.len()
+    }))
+}
+
+/// Split a file into variable-length content-defined chunks for dedup-aware
+/// copy/backup. See `chunk_content` for the rolling-hash boundary rule.
+///
+/// `min_size`/`avg_size`/`max_size` default to 4KB/16KB/64KB.
+#[pyfunction]
+#[pyo3(signature = (path, min_size=None, avg_size=None, max_size=None))]
+pub fn rust_chunk_file(path: String, min_size: Option<usize>, avg_size: Option<usize>, max_size: Option<usize>) -> PyResult<String> {
+    let min_size = min_size.unwrap_or(4 * 1024);
+    let avg_size = avg_size.unwrap_or(16 * 1024);
+    let max_size = max_size.unwrap_or(64 * 1024);
+
+    match fs::read(&path) {
+        Ok(data) => {
+            let total_size = data.len();
+            let chunks = chunk_content(&data, min_size, avg_size, max_size);
+
+            let chunk_list: Vec<serde_json::Value> = chunks
+                .iter()
+                .map(|(offset, length, hash)| {
+                    json!({
+                        "offset": offset,
+                        "length": length,
+                        "hash": format!("sha256:{}", hash)
+                    })
+                })
+                .collect();
+
+            let response = json!({
+                "success": true,
+                "result": {
+                    "path": path,
+                    "size": total_size,
+                    "chunk_count": chunk_list.len(),
+                    "chunks": chunk_list
+                },
+                "error": null
+            });
+            Ok(response.to_string())
+        }
+        Err(e) => {
+            let result = json!({
+                "success": false,
+                "result": null,
+                "error": format!("Failed to read file: {}", e)
+            });
+            Ok(result.to_string())
+        }
+    }
+}
+
+/// Recursively copy `source` into `dest`, creating directories as needed,
+/// preserving symlinks (recreating the link rather than following it and
+/// copying its target's contents), and counting files/bytes copied along
+/// the way so large copies are observable.
+fn copy_dir_recursive(source: &std::path::Path, dest: &std::path::Path, files_copied: &mut u64, bytes_copied: &mut u64) -> std::io::Result<()> {
+    fs::create_dir_all(dest)?;
+
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        let entry_path = entry.path();
+        let dest_path = dest.join(entry.file_name());
+        let file_type = entry.file_type()?;
+
+        if file_type.is_symlink() {
+            let target = fs::read_link(&entry_path)?;
+            #[cfg(unix)]
+            std::os::unix::fs::symlink(&target, &dest_path)?;
+            #[cfg(windows)]
+            {
+                if entry_path.is_dir() {
+                    std::os::windows::fs::symlink_dir(&target, &dest_path)?;
+                } else {
+                    std::os::windows::fs::symlink_file(&target, &dest_path)?;
+                }
+            }
+            *files_copied += 1;
+        } else if file_type.is_dir() {
+            copy_dir_recursive(&entry_path, &dest_path, files_copied, bytes_copied)?;
+        } else {
+            *bytes_copied += fs::copy(&entry_path, &dest_path)?;
+            *files_copied += 1;
+        }
+    }
+
+    Ok(())
+}
+
 /// Universal file manager - consolidated file operations tool
+///
+/// `dedup` (only honored by the `copy` action) switches to content-defined
+/// chunking: the source file is split into chunks and only chunks not
+/// already present in the destination's chunk store are written, so
+/// repeated backups of a large, slowly-changing file transfer less data.
+/// `recursive` mirrors `cp -r`: a directory source is only copied when it's
+/// set to true.
 #[pyfunction]
-pub fn rust_file_manager(action: String, path: String, content: Option<String>, to_path: Option<String>) -> PyResult<String> {
+#[pyo3(signature = (action, path, content=None, to_path=None, dedup=None, recursive=None))]
+pub fn rust_file_manager(action: String, path: String, content: Option<String>, to_path: Option<String>, dedup: Option<bool>, recursive: Option<bool>) -> PyResult<String> {
     let result = match action.as_str() {
         "list" => {
             // List directory contents
@@ -281,28 +872,10 @@ pub fn rust_file_manager(action: String, path: String, content: Option<String>,
             }
         },
         "read" => {
-            // Read file contents
-            match fs::read_to_string(&path) {
-                Ok(content) => {
-                    json!({
-                        "success": true,
-                        "result": {
-                            "path": path,
-                            "content": content,
-                            "size": content.len(),
-                            "encoding": "utf-8"
-                        },
-                        "error": null
-                    })
-                },
-                Err(e) => {
-                    json!({
-                        "success": false,
-                        "result": null,
-                        "error": format!("Failed to read file: {}", e)
-                    })
-                }
-            }
+            // Read file contents - shares rust_read_file's binary detection
+            // and result shape (type/encoding/mime_type) so both report the
+            // same thing for the same file.
+            read_file_contents(std::path::Path::new(&path))
         },
         "write" => {
             // Write file contents
@@ -404,7 +977,24 @@ pub fn rust_file_manager(action: String, path: String, content: Option<String>,
                     let _ = fs::create_dir_all(parent);
                 }
                 
-                if source_path.is_file() {
+                if source_path.is_file() && dedup.unwrap_or(false) {
+                    match copy_file_dedup(source_path, dest_path_obj, 4 * 1024, 16 * 1024, 64 * 1024) {
+                        Ok(result) => {
+                            json!({
+                                "success": true,
+                                "result": result,
+                                "error": null
+                            })
+                        },
+                        Err(e) => {
+                            json!({
+                                "success": false,
+                                "result": null,
+                                "error": format!("Failed to dedup-copy file: {}", e)
+                            })
+                        }
+                    }
+                } else if source_path.is_file() {
                     match fs::copy(&path, &dest_path) {
                         Ok(_) => {
                             json!({
@@ -421,12 +1011,38 @@ pub fn rust_file_manager(action: String, path: String, content: Option<String>,
                             })
                         }
                     }
+                } else if source_path.is_dir() {
+                    if !recursive.unwrap_or(false) {
+                        json!({
+                            "success": false,
+                            "result": null,
+                            "error": "Source is a directory; pass recursive=true to copy it"
+                        })
+                    } else {
+                        let mut files_copied = 0u64;
+                        let mut bytes_copied = 0u64;
+                        match copy_dir_recursive(source_path, dest_path_obj, &mut files_copied, &mut bytes_copied) {
+                            Ok(_) => {
+                                json!({
+                                    "success": true,
+                                    "result": {"from": path, "to": dest_path, "files_copied": files_copied, "bytes_copied": bytes_copied, "action": "directory_copied"},
+                                    "error": null
+                                })
+                            },
+                            Err(e) => {
+                                json!({
+                                    "success": false,
+                                    "result": null,
+                                    "error": format!("Failed to copy directory: {}", e)
+                                })
+                            }
+                        }
+                    }
                 } else {
-                    // For directories, we'd need a recursive copy function
                     json!({
                         "success": false,
                         "result": null,
-                        "error": "Directory copying not yet implemented in Rust backend"
+                        "error": format!("Source path does not exist: {}", path)
                     })
                 }
             }
@@ -527,85 +1143,104 @@ pub fn rust_file_manager(action: String, path: String, content: Option<String>,
     Ok(result.to_string())
 }
 
-/// Smart file search - consolidated search tool
-#[pyfunction]
-pub fn rust_file_search(query: String, search_path: String, search_type: String, extensions: Vec<String>) -> PyResult<String> {
-    use std::path::Path;
-    
-    let mut results = Vec::new();
-    
-    // Walk through directory tree
-    fn walk_dir(dir: &Path, query: &str, search_type: &str, extensions: &[String], results: &mut Vec<serde_json::Value>) -> std::io::Result<()> {
-        if dir.is_dir() {
-            for entry in fs::read_dir(dir)? {
-                let entry = entry?;
-                let path = entry.path();
-                
-                if path.is_dir() {
-                    walk_dir(&path, query, search_type, extensions, results)?;
-                } else if path.is_file() {
-                    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
-                    
-                    // Filter by extensions if specified
-                    if !extensions.is_empty() {
-                        if let Some(ext) = path.extension() {
-                            let file_ext = ext.to_string_lossy().to_lowercase();
-                            if !extensions.iter().any(|e| e.to_lowercase() == file_ext) {
-                                continue;
-                            }
-                        } else {
-                            continue;
-                        }
-                    }
-                    
-                    let mut match_score = 0;
-                    let mut match_reasons = Vec::new();
-                    
-                    // Search by filename
-                    if search_type == "name" || search_type == "both" {
-                        if file_name.to_lowercase().contains(&query.to_lowercase()) {
-                            match_score += 10;
-                            match_reasons.push("filename_contains");
-                        }
-                    }
-                    
-                    // Search by content (for text files)
-                    if search_type == "content" || search_type == "both" {
-                        if let Ok(content) = fs::read_to_string(&path) {
-                            if content.to_lowercase().contains(&query.to_lowercase()) {
-                                match_score += 15;
-                                match_reasons.push("content_contains");
-                                
-                                // Count occurrences
-                                let occurrences = content.to_lowercase().matches(&query.to_lowercase()).count();
-                                match_score += std::cmp::min(occurrences, 10);
-                            }
-                        }
-                    }
-                    
-                    if match_score > 0 {
-                        let metadata = fs::metadata(&path).ok();
-                        let file_info = json!({
-                            "path": path.to_string_lossy(),
-                            "name": file_name,
-                            "directory": path.parent().unwrap_or_else(|| Path::new("")).to_string_lossy(),
-                            "size": metadata.as_ref().map(|m| m.len()).unwrap_or(0),
-                            "modified": metadata.and_then(|m| m.modified().ok()).map(|t| 
-                                t.duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs()
-                            ),
-                            "match_score": match_score,
-                            "match_reasons": match_reasons
-                        });
-                        results.push(file_info);
-                    }
-                }
+/// Score a single file against the search query; returns `None` if it
+/// doesn't match the extension filter or the query at all.
+fn score_search_candidate(path: &std::path::Path, query: &str, search_type: &str, extensions: &[String]) -> Option<serde_json::Value> {
+    let file_name = path.file_name()?.to_string_lossy().to_string();
+
+    if !extensions.is_empty() {
+        let file_ext = path.extension()?.to_string_lossy().to_lowercase();
+        if !extensions.iter().any(|e| e.to_lowercase() == file_ext) {
+            return None;
+        }
+    }
+
+    let mut match_score = 0;
+    let mut match_reasons = Vec::new();
+
+    if search_type == "name" || search_type == "both" {
+        if file_name.to_lowercase().contains(&query.to_lowercase()) {
+            match_score += 10;
+            match_reasons.push("filename_contains");
+        }
+    }
+
+    if search_type == "content" || search_type == "both" {
+        if let Ok(content) = fs::read_to_string(path) {
+            if content.to_lowercase().contains(&query.to_lowercase()) {
+                match_score += 15;
+                match_reasons.push("content_contains");
+
+                let occurrences = content.to_lowercase().matches(&query.to_lowercase()).count();
+                match_score += std::cmp::min(occurrences, 10);
             }
         }
-        Ok(())
     }
-    
+
+    if match_score == 0 {
+        return None;
+    }
+
+    let metadata = fs::metadata(path).ok();
+    Some(json!({
+        "path": path.to_string_lossy(),
+        "name": file_name,
+        "directory": path.parent().unwrap_or_else(|| std::path::Path::new("")).to_string_lossy(),
+        "size": metadata.as_ref().map(|m| m.len()).unwrap_or(0),
+        "modified": metadata.and_then(|m| m.modified().ok()).map(|t|
+            t.duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs()
+        ),
+        "match_score": match_score,
+        "match_reasons": match_reasons
+    }))
+}
+
+/// Build a gitignore-style matcher combining `exclude_globs` with, when
+/// `ignore_vcs` is set, `.git`/`.hg`/`.svn`. Returns `None` when there's
+/// nothing extra to exclude, so the walker's own `.gitignore`/`.ignore`
+/// handling (already on by default) is all that applies.
+fn build_exclude_matcher(search_root: &std::path::Path, exclude_globs: &[String], ignore_vcs: bool) -> Option<ignore::gitignore::Gitignore> {
+    if exclude_globs.is_empty() && !ignore_vcs {
+        return None;
+    }
+
+    let mut builder = ignore::gitignore::GitignoreBuilder::new(search_root);
+    if ignore_vcs {
+        let _ = builder.add_line(None, ".git/");
+        let _ = builder.add_line(None, ".hg/");
+        let _ = builder.add_line(None, ".svn/");
+    }
+    for pattern in exclude_globs {
+        let _ = builder.add_line(None, pattern);
+    }
+    builder.build().ok()
+}
+
+/// Smart file search - consolidated search tool
+///
+/// Walks `search_path` with `ignore::WalkBuilder` so `.gitignore`/`.ignore`
+/// rules are honored like they would be in an editor or `rg`, plus a
+/// caller-supplied `exclude_globs` (gitignore-syntax patterns, e.g.
+/// `*.log`, `build/`, `**/tmp`) and `ignore_vcs` (default true) to also
+/// prune `.git`/`.hg`/`.svn`. A directory matching an exclude pattern has
+/// its whole subtree skipped rather than just itself. The surviving files
+/// are scored in parallel with rayon since content search is the expensive
+/// part.
+#[pyfunction]
+#[pyo3(signature = (query, search_path, search_type, extensions, exclude_globs=None, ignore_vcs=None))]
+pub fn rust_file_search(
+    query: String,
+    search_path: String,
+    search_type: String,
+    extensions: Vec<String>,
+    exclude_globs: Option<Vec<String>>,
+    ignore_vcs: Option<bool>,
+) -> PyResult<String> {
+    use rayon::prelude::*;
+    use std::path::Path;
+
     let search_path_obj = Path::new(&search_path);
-    
+
     if !search_path_obj.exists() {
         return Ok(json!({
             "success": false,
@@ -613,36 +1248,54 @@ pub fn rust_file_search(query: String, search_path: String, search_type: String,
             "error": format!("Search path does not exist: {}", search_path)
         }).to_string());
     }
-    
-    if let Err(e) = walk_dir(search_path_obj, &query, &search_type, &extensions, &mut results) {
-        return Ok(json!({
-            "success": false,
-            "result": null,
-            "error": format!("Search failed: {}", e)
-        }).to_string());
+
+    let exclude_globs = exclude_globs.unwrap_or_default();
+    let ignore_vcs = ignore_vcs.unwrap_or(true);
+    let matcher = build_exclude_matcher(search_path_obj, &exclude_globs, ignore_vcs);
+
+    let mut walk_builder = ignore::WalkBuilder::new(search_path_obj);
+    if let Some(matcher) = matcher {
+        walk_builder.filter_entry(move |entry| {
+            let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+            !matcher.matched(entry.path(), is_dir).is_ignore()
+        });
     }
-    
+
+    let candidates: Vec<std::path::PathBuf> = walk_builder
+        .build()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().map(|t| t.is_file()).unwrap_or(false))
+        .map(|entry| entry.into_path())
+        .collect();
+
+    let mut results: Vec<serde_json::Value> = candidates
+        .par_iter()
+        .filter_map(|path| score_search_candidate(path, &query, &search_type, &extensions))
+        .collect();
+
     // Sort by match score (highest first)
     results.sort_by(|a, b| {
         let score_a = a["match_score"].as_i64().unwrap_or(0);
         let score_b = b["match_score"].as_i64().unwrap_or(0);
         score_b.cmp(&score_a)
     });
-    
+
     // Limit to top 50 results
     results.truncate(50);
-    
+
     let response = json!({
         "success": true,
         "result": {
             "query": query,
             "search_path": search_path,
             "search_type": search_type,
+            "exclude_globs": exclude_globs,
+            "ignore_vcs": ignore_vcs,
             "files_found": results.len(),
             "results": results
         },
         "error": null
     });
-    
+
     Ok(response.to_string())
 }